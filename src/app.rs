@@ -84,6 +84,10 @@ pub struct App {
     #[structopt(long, value_name = "SS58_PREFIX", default_value = "42")]
     pub ss58_prefix: sp_core::crypto::Ss58AddressFormat,
 
+    /// Parse and display the balances in plancks instead of tokens.
+    #[structopt(long, global = true)]
+    pub raw: bool,
+
     #[structopt(subcommand)]
     pub command: Command,
 }
@@ -95,6 +99,7 @@ impl App {
 
     pub async fn run(self) -> Result<()> {
         sp_core::crypto::set_default_ss58_version(self.ss58_prefix);
+        crate::token::set_raw(self.raw);
 
         let signer = if let Some(ref uri) = self.get_uri() {
             as_sr25519_signer(uri)?
@@ -1,22 +1,97 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use codec::{Decode, Encode};
 use jsonrpsee_types::{to_json_value, Subscription};
-use subxt::{Client, ClientBuilder, Metadata, RpcClient, Store};
+use serde::{Deserialize, Deserializer};
+use subxt::{system::Phase, Call, Client, ClientBuilder, Metadata, Raw, RpcClient, Store};
 
-use sp_core::{storage::StorageChangeSet, Bytes, H256};
-use sp_runtime::traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT};
+use frame_support::weights::DispatchClass;
+use sp_core::{
+    storage::{StorageChangeSet, StorageData, StorageKey},
+    Bytes, H256,
+};
+use sp_runtime::{
+    traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT},
+    OpaqueExtrinsic,
+};
 
 use cp_permastore::CHUNK_SIZE;
-use pallet_poa::DepthInfo;
 
 use crate::{
-    pallets::{permastore::StoreCallExt, poa::HistoryDepthStore},
+    pallets::{
+        permastore::{StoreCall, StoreCallExt, StoredEventExt},
+        poa::HistoryDepthStore,
+    },
     runtime::{
-        primitives::{AccountId, BlockNumber, Hash},
+        extrinsic::{is_extrinsic_success, DecodedExtrinsic},
+        metadata::{pallet_constants, ArgumentRegistry, Constant},
+        primitives::{AccountId, Balance, Block, BlockNumber, Hash, Header},
         CanyonRuntime, CanyonSigner,
     },
+    token::Token,
+    utils::{compute_chunk_root, final_storage_prefix},
 };
 
+/// A successful `permastore::store` extrinsic included in a block.
+#[derive(Clone, Debug)]
+pub struct StoreTransaction {
+    /// Number of the block including the extrinsic.
+    pub block_number: BlockNumber,
+    /// Hash of the block including the extrinsic.
+    pub block_hash: Hash,
+    /// Parent hash of the block including the extrinsic.
+    pub parent_hash: Hash,
+    /// Index of the extrinsic in the block.
+    pub extrinsic_index: u32,
+    /// Signer of the extrinsic.
+    pub signer: Option<AccountId>,
+    /// Byte size of the stored data.
+    pub data_size: u32,
+    /// Chunk root of the stored data.
+    pub chunk_root: Hash,
+    /// The opaque extrinsic.
+    pub extrinsic: OpaqueExtrinsic,
+}
+
+/// Fee information of an extrinsic returned by `payment_queryInfo`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeDispatchInfo {
+    /// Weight of the extrinsic.
+    pub weight: u64,
+    /// Dispatch class of the extrinsic.
+    pub class: DispatchClass,
+    /// The inclusion fee of the extrinsic, tip excluded.
+    #[serde(deserialize_with = "deserialize_balance")]
+    pub partial_fee: Balance,
+}
+
+/// Transaction data stored on the connected node.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredDataInfo {
+    /// Chunk root of the data.
+    pub chunk_root: Hash,
+    /// Byte size of the data.
+    pub data_size: u64,
+    /// Number of the block in which the data was stored, if known.
+    pub block_number: Option<BlockNumber>,
+}
+
+/// Balances are serialized either as a number or as a string depending on the node version.
+fn deserialize_balance<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Balance, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u64),
+        String(String),
+    }
+
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(n) => Ok(n.into()),
+        NumberOrString::String(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
+
 /// Unit type wrapper of `Client` for Canyon runtime.
 #[derive(Clone)]
 pub struct CanyonClient(pub Client<CanyonRuntime>);
@@ -29,6 +104,16 @@ impl CanyonClient {
             .skip_type_sizes_check()
             .build()
             .await?;
+        // The runtime may have types unknown to this tool, they only prevent decoding the
+        // events using them, so they are logged instead of refusing to connect.
+        if let Err(missing) = client.events_decoder().check_missing_type_sizes() {
+            let mut missing = missing.into_iter().collect::<Vec<_>>();
+            missing.sort();
+            log::warn!(
+                "No type size registered for {}, the events using them can not be decoded",
+                missing.join(", ")
+            );
+        }
         Ok(Self(client))
     }
 
@@ -42,6 +127,65 @@ impl CanyonClient {
         self.0.metadata()
     }
 
+    /// Returns the native token of the connected chain.
+    pub fn token(&self) -> Token {
+        Token::new(self.0.properties())
+    }
+
+    /// Returns the value of constant `name` in pallet `module` from the chain metadata.
+    pub fn constant<V: Decode>(&self, module: &str, name: &str) -> Result<V> {
+        Ok(self.metadata().module(module)?.constant(name)?.value()?)
+    }
+
+    /// Returns the chunk size of the connected chain.
+    ///
+    /// Falls back to the compiled `CHUNK_SIZE` if the runtime does not expose the constant.
+    pub fn chunk_size(&self) -> u32 {
+        self.constant("Permastore", "ChunkSize")
+            .unwrap_or(CHUNK_SIZE)
+    }
+
+    /// Returns the existential deposit of the connected chain.
+    pub fn existential_deposit(&self) -> Result<Balance> {
+        self.constant("Balances", "ExistentialDeposit")
+    }
+
+    /// Returns the maximum weight of a normal extrinsic of the connected chain.
+    pub fn max_extrinsic_weight(&self) -> Result<u64> {
+        // `BlockWeights` starts with `base_block`, `max_block` and the weights of the normal
+        // class, i.e. `base_extrinsic`, `max_extrinsic` and `max_total`.
+        let (_, max_block, _, max_extrinsic, max_total) =
+            self.constant::<(u64, u64, u64, Option<u64>, Option<u64>)>("System", "BlockWeights")?;
+        Ok(max_extrinsic.or(max_total).unwrap_or(max_block))
+    }
+
+    /// Returns the maximum byte length of a normal extrinsic of the connected chain.
+    pub fn max_extrinsic_length(&self) -> Result<u32> {
+        // `BlockLength` is the maximum length per dispatch class, starting with the normal one.
+        self.constant("System", "BlockLength")
+    }
+
+    /// Returns the chunk size of the connected chain, fails if it differs from the
+    /// compiled `CHUNK_SIZE` unless `allow_mismatch` is set.
+    pub fn checked_chunk_size(&self, allow_mismatch: bool) -> Result<u32> {
+        let chunk_size = self.chunk_size();
+        if chunk_size != CHUNK_SIZE {
+            if !allow_mismatch {
+                return Err(anyhow!(
+                    "Chunk size of the chain is {} bytes but the local one is {} bytes, \
+                    rerun with `--allow-chunk-size-mismatch` to proceed anyway.",
+                    chunk_size,
+                    CHUNK_SIZE
+                ));
+            }
+            eprintln!(
+                "Warning: using the chunk size {} of the chain instead of the local one {}",
+                chunk_size, CHUNK_SIZE
+            );
+        }
+        Ok(chunk_size)
+    }
+
     /// Returns the rpc client.
     pub fn rpc_client(&self) -> &RpcClient {
         self.0.rpc_client()
@@ -65,19 +209,181 @@ impl CanyonClient {
             .map(|signed_block| *signed_block.block.header().number()))
     }
 
-    /// Send `permastore::call` extrinsic.
-    pub async fn store(&self, signer: &CanyonSigner, data: Vec<u8>) -> Result<()> {
-        let chunks = data
-            .chunks(CHUNK_SIZE as usize)
-            .map(|c| BlakeTwo256::hash(c).encode())
-            .collect();
+    /// Returns the number of the best block.
+    pub async fn best_number(&self) -> Result<BlockNumber> {
+        self.0
+            .header(None::<Hash>)
+            .await?
+            .map(|header| *header.number())
+            .ok_or_else(|| anyhow!("Best block header not found"))
+    }
+
+    /// Returns the hash and the header given block number.
+    pub async fn header_at(&self, number: BlockNumber) -> Result<(Hash, Header)> {
+        let hash = self
+            .block_hash(Some(number))
+            .await?
+            .ok_or_else(|| anyhow!("Block #{} not found", number))?;
+        let header = self
+            .0
+            .header(Some(hash))
+            .await?
+            .ok_or_else(|| anyhow!("Header of block {:?} not found", hash))?;
+        Ok((hash, header))
+    }
+
+    /// Returns the hash and the block given block number.
+    pub async fn block_at(&self, number: BlockNumber) -> Result<(Hash, Block)> {
+        let hash = self
+            .block_hash(Some(number))
+            .await?
+            .ok_or_else(|| anyhow!("Block #{} not found", number))?;
+        Ok((hash, self.block_by_hash(hash).await?))
+    }
+
+    /// Returns the block given block hash.
+    pub async fn block_by_hash(&self, hash: Hash) -> Result<Block> {
+        let signed_block = self
+            .0
+            .block(Some(hash))
+            .await?
+            .ok_or_else(|| anyhow!("Block {:?} not found", hash))?;
+        Ok(signed_block.block)
+    }
+
+    /// Returns the index of extrinsic `extrinsic_hash` in the block `at`.
+    pub async fn extrinsic_index(&self, at: Hash, extrinsic_hash: Hash) -> Result<Option<u32>> {
+        let signed_block = self
+            .0
+            .block(Some(at))
+            .await?
+            .ok_or_else(|| anyhow!("Block {:?} not found", at))?;
+        Ok(signed_block
+            .block
+            .extrinsics
+            .iter()
+            .position(|xt| BlakeTwo256::hash_of(xt) == extrinsic_hash)
+            .map(|index| index as u32))
+    }
+
+    /// Returns the events emitted in the block `at`.
+    pub async fn events(&self, at: Hash) -> Result<Vec<(Phase, Raw)>> {
+        let data = self.events_data(at).await?;
+        self.decode_events(&data)
+    }
+
+    /// Returns the SCALE encoded events emitted in the block `at`.
+    pub async fn events_data(&self, at: Hash) -> Result<Vec<u8>> {
+        let key = StorageKey(final_storage_prefix("System", "Events"));
+        Ok(self
+            .state_get_storage(key, Some(at))
+            .await?
+            .map(|data| data.0)
+            .unwrap_or_default())
+    }
+
+    /// Decodes the SCALE encoded events `data` returned by `events_data`.
+    pub fn decode_events(&self, data: &[u8]) -> Result<Vec<(Phase, Raw)>> {
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(self.0.events_decoder().decode_events(&mut &data[..])?)
+    }
+
+    /// Returns the inclusion fee of `call` signed by `signer` at the best block.
+    pub async fn estimate_fee<C: Call<CanyonRuntime> + Send + Sync>(
+        &self,
+        call: C,
+        signer: &CanyonSigner,
+    ) -> Result<Balance> {
+        let uxt = self.0.create_signed(call, signer).await?;
+        Ok(self
+            .payment_query_info(uxt.encode().into(), None)
+            .await?
+            .partial_fee)
+    }
+
+    /// Returns the registry of the call and event arguments of the runtime at block `at`.
+    pub async fn argument_registry(&self, at: Option<Hash>) -> Result<ArgumentRegistry> {
+        let metadata = self.state_get_metadata(at).await?;
+        ArgumentRegistry::from_metadata(&metadata.0)
+    }
+
+    /// Returns the constants of `pallet` in the runtime at block `at`.
+    pub async fn pallet_constants(&self, pallet: &str, at: Option<Hash>) -> Result<Vec<Constant>> {
+        let metadata = self.state_get_metadata(at).await?;
+        pallet_constants(&metadata.0, pallet)
+    }
+
+    /// Returns the pallet index and call index of `permastore::store`.
+    pub fn store_call_index(&self) -> Result<[u8; 2]> {
+        let encoded = self
+            .0
+            .encode(StoreCall::<CanyonRuntime>::new(0, Default::default()))?;
+        Ok([encoded.0[0], encoded.0[1]])
+    }
+
+    /// Returns the successful `permastore::store` extrinsics in block `number`.
+    pub async fn store_transactions(&self, number: BlockNumber) -> Result<Vec<StoreTransaction>> {
+        let store_call_index = self.store_call_index()?;
+        let (block_hash, block) = self.block_at(number).await?;
+        let parent_hash = block.header.parent_hash;
+
+        let mut store_calls = Vec::new();
+        for (index, xt) in block.extrinsics.into_iter().enumerate() {
+            let decoded = DecodedExtrinsic::decode(&xt)?;
+            if let Some((data_size, chunk_root)) =
+                decoded.decode_call::<(u32, Hash)>(store_call_index)
+            {
+                store_calls.push((
+                    index as u32,
+                    decoded.signer().cloned(),
+                    data_size,
+                    chunk_root,
+                    xt,
+                ));
+            }
+        }
+
+        // Only fetch the events of the blocks containing `store` calls.
+        if store_calls.is_empty() {
+            return Ok(Vec::new());
+        }
+        let events = self.events(block_hash).await?;
 
-        let chunk_root = BlakeTwo256::ordered_trie_root(chunks);
+        Ok(store_calls
+            .into_iter()
+            .filter(|(index, ..)| is_extrinsic_success(&events, *index))
+            .map(
+                |(extrinsic_index, signer, data_size, chunk_root, extrinsic)| StoreTransaction {
+                    block_number: number,
+                    block_hash,
+                    parent_hash,
+                    extrinsic_index,
+                    signer,
+                    data_size,
+                    chunk_root,
+                    extrinsic,
+                },
+            )
+            .collect())
+    }
+
+    /// Send `permastore::call` extrinsic, `data` is split in chunks of `chunk_size` bytes.
+    pub async fn store(&self, signer: &CanyonSigner, data: Vec<u8>, chunk_size: u32) -> Result<()> {
+        let chunk_root = compute_chunk_root(&data, chunk_size);
         let data_size = data.len() as u32;
         println!("data size: {:?}, chunk root: {:?}", data_size, chunk_root);
 
-        let result = self.0.store(signer, data_size, chunk_root).await?;
-        println!("Stored result: {:?}", result);
+        let result = self
+            .0
+            .store_and_watch(signer, data_size, chunk_root)
+            .await?;
+        if let Some(event) = result.stored()? {
+            println!("Stored by {} in block {:?}", event.who, result.block);
+        } else {
+            println!("Failed to find Permastore::Stored Event");
+        }
 
         Ok(())
     }
@@ -85,6 +391,70 @@ impl CanyonClient {
 
 ////    RPC implementations
 impl CanyonClient {
+    ///////////////////////////////////////////////////////////////////////
+    ////    State
+    ///////////////////////////////////////////////////////////////////////
+    /// Returns the raw storage value of `key` at block `at`.
+    pub async fn state_get_storage(
+        &self,
+        key: StorageKey,
+        at: Option<Hash>,
+    ) -> Result<Option<StorageData>> {
+        let params = &[to_json_value(key)?, to_json_value(at)?];
+        let data = self
+            .rpc_client()
+            .request("state_getStorage", params)
+            .await?;
+        Ok(data)
+    }
+
+    /// Returns the SCALE encoded runtime metadata at block `at`.
+    pub async fn state_get_metadata(&self, at: Option<Hash>) -> Result<Bytes> {
+        let params = &[to_json_value(at)?];
+        let data = self
+            .rpc_client()
+            .request("state_getMetadata", params)
+            .await?;
+        Ok(data)
+    }
+
+    /// Returns the changes of the storage `keys` in the blocks from `from` to `to`, the first
+    /// change set contains the values at `from`.
+    pub async fn state_query_storage(
+        &self,
+        keys: Vec<StorageKey>,
+        from: Hash,
+        to: Option<Hash>,
+    ) -> Result<Vec<StorageChangeSet<Hash>>> {
+        let params = &[
+            to_json_value(keys)?,
+            to_json_value(from)?,
+            to_json_value(to)?,
+        ];
+        let data = self
+            .rpc_client()
+            .request("state_queryStorage", params)
+            .await?;
+        Ok(data)
+    }
+
+    ///////////////////////////////////////////////////////////////////////
+    ////    Payment
+    ///////////////////////////////////////////////////////////////////////
+    /// Returns the fee information of the encoded extrinsic at block `at`.
+    pub async fn payment_query_info(
+        &self,
+        extrinsic: Bytes,
+        at: Option<Hash>,
+    ) -> Result<RuntimeDispatchInfo> {
+        let params = &[to_json_value(extrinsic)?, to_json_value(at)?];
+        let data = self
+            .rpc_client()
+            .request("payment_queryInfo", params)
+            .await?;
+        Ok(data)
+    }
+
     ///////////////////////////////////////////////////////////////////////
     ////    Permastore
     ///////////////////////////////////////////////////////////////////////
@@ -108,6 +478,32 @@ impl CanyonClient {
         Ok(data)
     }
 
+    /// Retrieve the transaction data given chunk root.
+    pub async fn permastore_retrieve_data(&self, chunk_root: Hash) -> Result<Option<Bytes>> {
+        let params = &[to_json_value(chunk_root)?];
+        let data = self
+            .rpc_client()
+            .request("permastore_retrieveData", params)
+            .await?;
+        Ok(data)
+    }
+
+    /// List the transaction data stored on the node.
+    pub async fn permastore_list_data(&self) -> Result<Vec<StoredDataInfo>> {
+        let data = self
+            .rpc_client()
+            .request("permastore_listData", &[])
+            .await
+            .map_err(|err| {
+                anyhow!(
+                    "Failed to list the data stored on node, \
+                    make sure the node exposes `permastore_listData`: {:?}",
+                    err
+                )
+            })?;
+        Ok(data)
+    }
+
     /// Remove the transaction data given chunk root.
     pub async fn permastore_remove_data(&self, chunk_root: Hash) -> Result<bool> {
         let params = &[to_json_value(chunk_root)?];
@@ -122,7 +518,7 @@ impl CanyonClient {
     ////    Poa
     ///////////////////////////////////////////////////////////////////////
     /// Watch Poa HistoryDepth.
-    async fn watch_poa_history_depth(
+    pub async fn watch_poa_history_depth(
         &self,
         who: &AccountId,
     ) -> Result<Subscription<StorageChangeSet<Hash>>> {
@@ -140,33 +536,4 @@ impl CanyonClient {
 
         Ok(subscription)
     }
-
-    /// Subscribe to Poa HistoryDepth.
-    pub async fn subscribe_poa_history_depth(
-        &self,
-        who: &AccountId,
-    ) -> Result<Subscription<StorageChangeSet<Hash>>> {
-        let mut subscription = self.watch_poa_history_depth(who).await?;
-
-        while let Ok(Some(StorageChangeSet { block, changes })) = subscription.next().await {
-            if !changes.is_empty() {
-                // We only subscribed one key.
-                let (_storage_key, storage_data) = &changes[0];
-                if let Some(data) = storage_data {
-                    let new_depth_info: DepthInfo<BlockNumber> =
-                        Decode::decode(&mut data.0.as_slice())?;
-                    let number = self.block_number(block).await?.unwrap_or_default();
-                    println!(
-                        "block #{}: {}, new_depth_info: {:?}, estimated storage ratio: {}",
-                        number,
-                        block,
-                        new_depth_info,
-                        crate::command::poa::display_storage_ratio(&new_depth_info)
-                    );
-                }
-            }
-        }
-
-        Ok(subscription)
-    }
 }
@@ -0,0 +1,260 @@
+use anyhow::{anyhow, Result};
+use codec::Decode;
+use structopt::StructOpt;
+use subxt::{
+    balances::AccountData,
+    system::{AccountInfo, AccountStore},
+    Raw, RawEvent, Store,
+};
+
+use crate::{
+    client::CanyonClient,
+    runtime::{
+        extrinsic::DecodedExtrinsic,
+        primitives::{AccountId, Balance, BlockNumber},
+        CanyonRuntime,
+    },
+    token::Token,
+    utils::{parse_account, OutputFormat},
+};
+
+/// Maximum number of blocks queried in a single `state_queryStorage` request.
+const QUERY_STORAGE_RANGE: BlockNumber = 1000;
+
+#[derive(Debug, StructOpt)]
+pub struct History {
+    /// Account to trace the balance history of.
+    #[structopt(index = 1, parse(try_from_str = parse_account))]
+    who: AccountId,
+    /// First block of the range.
+    #[structopt(long)]
+    from: BlockNumber,
+    /// Last block of the range, defaults to the best block.
+    #[structopt(long)]
+    to: Option<BlockNumber>,
+    /// Output format.
+    #[structopt(
+        long,
+        possible_values = &OutputFormat::variants(),
+        case_insensitive = true,
+        default_value = "csv"
+    )]
+    format: OutputFormat,
+}
+
+/// Returns the signed `change` with the token unit.
+fn display_change(token: &Token, change: i128) -> String {
+    if change < 0 {
+        format!("-{}", token.display(change.unsigned_abs()))
+    } else {
+        format!("+{}", token.display(change as u128))
+    }
+}
+
+/// Returns the description of the `Balances` event concerning `who` and its effect on the
+/// free balance of `who`, `None` if the event does not concern `who`.
+fn describe_event(
+    token: &Token,
+    who: &AccountId,
+    event: &RawEvent,
+    previous: &AccountData<Balance>,
+) -> Result<Option<(String, i128)>> {
+    if event.module != "Balances" {
+        return Ok(None);
+    }
+
+    let data = &mut event.data.as_slice();
+    let described = match event.variant.as_str() {
+        "Transfer" => {
+            let (from, to, amount) = <(AccountId, AccountId, Balance)>::decode(data)?;
+            if &from == who && &to == who {
+                Some((format!("transfer to self {}", token.display(amount)), 0))
+            } else if &from == who {
+                Some((
+                    format!("transfer {} to {}", token.display(amount), to),
+                    -(amount as i128),
+                ))
+            } else if &to == who {
+                Some((
+                    format!("transfer {} from {}", token.display(amount), from),
+                    amount as i128,
+                ))
+            } else {
+                None
+            }
+        }
+        "ReserveRepatriated" => {
+            let (from, to, amount, status) = <(AccountId, AccountId, Balance, u8)>::decode(data)?;
+            // Status `0` means the balance is repatriated to the free balance of `to`.
+            if &to == who {
+                let effect = if status == 0 { amount as i128 } else { 0 };
+                Some((
+                    format!("repatriated {} from {}", token.display(amount), from),
+                    effect,
+                ))
+            } else if &from == who {
+                Some((
+                    format!("reserve {} repatriated to {}", token.display(amount), to),
+                    0,
+                ))
+            } else {
+                None
+            }
+        }
+        "BalanceSet" => {
+            let (account, free, reserved) = <(AccountId, Balance, Balance)>::decode(data)?;
+            if &account == who {
+                Some((
+                    format!(
+                        "balance set to free {}, reserved {}",
+                        token.display(free),
+                        token.display(reserved)
+                    ),
+                    free as i128 - previous.free as i128,
+                ))
+            } else {
+                None
+            }
+        }
+        variant @ ("Endowed" | "DustLost" | "Deposit" | "Reserved" | "Unreserved") => {
+            let (account, amount) = <(AccountId, Balance)>::decode(data)?;
+            if &account == who {
+                let amount_display = token.display(amount);
+                let amount = amount as i128;
+                // An account is endowed by a transfer or a deposit which already accounts for it.
+                let (description, effect) = match variant {
+                    "Endowed" => ("endowed", 0),
+                    "DustLost" => ("dust lost", -amount),
+                    "Deposit" => ("deposit", amount),
+                    "Reserved" => ("reserve", -amount),
+                    _ => ("unreserve", amount),
+                };
+                Some((format!("{} {}", description, amount_display), effect))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+
+    Ok(described)
+}
+
+impl History {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let token = client.token();
+        let to = match self.to {
+            Some(to) => to,
+            None => client.best_number().await?,
+        };
+        let key = AccountStore::<CanyonRuntime> {
+            account_id: &self.who,
+        }
+        .key(client.metadata())?;
+
+        let mut previous: Option<AccountData<Balance>> = None;
+        let mut rows = Vec::new();
+
+        let mut start = self.from;
+        while start <= to {
+            let end = start.saturating_add(QUERY_STORAGE_RANGE - 1).min(to);
+            let from_hash = client
+                .block_hash(Some(start))
+                .await?
+                .ok_or_else(|| anyhow!("Block #{} not found", start))?;
+            let to_hash = client.block_hash(Some(end)).await?;
+
+            let change_sets = client
+                .state_query_storage(vec![key.clone()], from_hash, to_hash)
+                .await?;
+            for change_set in change_sets {
+                for (_, data) in change_set.changes {
+                    // A missing account is a reaped one.
+                    let data = match data {
+                        Some(data) => {
+                            AccountInfo::<CanyonRuntime>::decode(&mut data.0.as_slice())?.data
+                        }
+                        None => Default::default(),
+                    };
+                    if previous.as_ref() == Some(&data) {
+                        continue;
+                    }
+
+                    let block = client.block_by_hash(change_set.block).await?;
+                    let mut row = vec![
+                        block.header.number.to_string(),
+                        format!("{:?}", change_set.block),
+                        token.display(data.free),
+                        token.display(data.reserved),
+                    ];
+
+                    match previous {
+                        None => row.extend(vec![String::new(), String::new(), "initial".into()]),
+                        Some(ref previous) => {
+                            let mut descriptions = Vec::new();
+                            let mut explained = 0i128;
+                            for (_, raw) in client.events(change_set.block).await? {
+                                if let Raw::Event(event) = raw {
+                                    if let Some((description, effect)) =
+                                        describe_event(&token, &self.who, &event, previous)?
+                                    {
+                                        descriptions.push(description);
+                                        explained += effect;
+                                    }
+                                }
+                            }
+
+                            // The transaction fees and tips do not emit events in the
+                            // balances pallet, the unexplained change is attributed to them
+                            // if `who` signed an extrinsic in the block.
+                            let free_change = data.free as i128 - previous.free as i128;
+                            let unexplained = free_change - explained;
+                            if unexplained != 0 {
+                                let signed = block.extrinsics.iter().any(|xt| {
+                                    DecodedExtrinsic::decode(xt)
+                                        .map(|decoded| decoded.signer() == Some(&self.who))
+                                        .unwrap_or(false)
+                                });
+                                let description = if signed { "fee" } else { "other" };
+                                descriptions.push(format!(
+                                    "{} {}",
+                                    description,
+                                    display_change(&token, unexplained)
+                                ));
+                            }
+
+                            row.extend(vec![
+                                display_change(&token, free_change),
+                                display_change(
+                                    &token,
+                                    data.reserved as i128 - previous.reserved as i128,
+                                ),
+                                descriptions.join("; "),
+                            ]);
+                        }
+                    }
+
+                    rows.push(row);
+                    previous = Some(data);
+                }
+            }
+
+            start = end + 1;
+        }
+
+        self.format.print(
+            &[
+                "block_number",
+                "block_hash",
+                "free",
+                "reserved",
+                "free_change",
+                "reserved_change",
+                "events",
+            ],
+            &rows,
+        );
+
+        Ok(())
+    }
+}
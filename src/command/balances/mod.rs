@@ -0,0 +1,92 @@
+use anyhow::Result;
+use structopt::StructOpt;
+
+use subxt::balances::LocksStoreExt;
+
+mod history;
+mod pay_batch;
+mod show;
+mod transfer;
+mod watch;
+
+use crate::{
+    client::CanyonClient,
+    runtime::{
+        primitives::{AccountId, BlockNumber},
+        CanyonSigner,
+    },
+    utils::parse_account,
+};
+
+/// Balances
+#[derive(Debug, StructOpt)]
+pub enum Balances {
+    /// Transfer some balances from signer to another account.
+    Transfer(transfer::Transfer),
+    /// Transfer some balances from signer to another account, failing if the signer
+    /// would be reaped.
+    TransferKeepAlive(transfer::Transfer),
+    /// Transfer the entire transferable balance of signer to another account.
+    TransferAll(transfer::TransferAll),
+    /// Transfer some balances between two accounts, signer must be the sudo key.
+    ForceTransfer(transfer::ForceTransfer),
+    /// Set the free and reserved balances of an account, signer must be the sudo key.
+    SetBalance(transfer::SetBalance),
+    /// Pay many accounts from a CSV file in `utility::batch_all` calls.
+    PayBatch(pay_batch::PayBatch),
+    /// Show the free, reserved, frozen and transferable balances of accounts.
+    Show(show::Show),
+    /// Trace the balance changes of an account over a range of blocks.
+    History(history::History),
+    /// Watch the finalized blocks for the transfers to or from some accounts.
+    Watch(watch::Watch),
+    /// Inspect the balances storage items.
+    Storage(Storage),
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Storage {
+    /// Any liquidity locks on some account balances.
+    Locks {
+        #[structopt(index = 1, long, parse(try_from_str = parse_account))]
+        who: AccountId,
+        #[structopt(long)]
+        block_number: Option<BlockNumber>,
+    },
+}
+
+impl Balances {
+    pub async fn run(self, url: String, signer: CanyonSigner) -> Result<()> {
+        let client = CanyonClient::create(url.clone()).await?;
+        let token = client.token();
+
+        match self {
+            Balances::Transfer(transfer) => transfer.run(&client, &signer, false).await?,
+            Balances::TransferKeepAlive(transfer) => transfer.run(&client, &signer, true).await?,
+            Balances::TransferAll(transfer_all) => transfer_all.run(&client, &signer).await?,
+            Balances::ForceTransfer(force_transfer) => force_transfer.run(&client, &signer).await?,
+            Balances::SetBalance(set_balance) => set_balance.run(&client, &signer).await?,
+            Balances::PayBatch(pay_batch) => pay_batch.run(&client, &signer).await?,
+            Balances::Show(show) => show.run(&client).await?,
+            Balances::History(history) => history.run(&client).await?,
+            Balances::Watch(watch) => watch.run(&url).await?,
+            Balances::Storage(storage) => match storage {
+                Storage::Locks { who, block_number } => {
+                    let at = client.block_hash(block_number).await?;
+                    let locks = client.0.locks(&who, at).await?;
+                    println!("Locks of {}:", who);
+                    for lock in locks {
+                        println!(
+                            "  {}: {}, reasons: {:?}",
+                            show::display_lock_id(&lock.id),
+                            token.display(lock.amount),
+                            lock.reasons
+                        );
+                    }
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+use std::{fs::OpenOptions, io::Write, marker::PhantomData, ops::Range, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use codec::Encode;
+use structopt::StructOpt;
+use subxt::{balances::TransferCall, system::AccountStoreExt, Encoded, RawEvent, Signer};
+
+use crate::{
+    client::CanyonClient,
+    command::balances::transfer::{checked_sum, proceed, receiver_warning, sender_warning},
+    pallets::utility::{BatchAllCall, BatchAllCallExt},
+    runtime::{
+        primitives::{AccountId, Address, Balance},
+        CanyonRuntime, CanyonSigner,
+    },
+    token::{Amount, Token},
+    utils::{csv_record, parse_account, read_lines},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct PayBatch {
+    /// CSV file of `account,amount` rows, amounts are parsed as in `transfer`.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    csv: PathBuf,
+    /// CSV file to write the receipt of each payout to.
+    #[structopt(
+        long,
+        value_name = "PATH",
+        parse(from_os_str),
+        default_value = "receipt.csv"
+    )]
+    receipt: PathBuf,
+    /// Overwrite the receipt file if it already exists.
+    #[structopt(long)]
+    overwrite: bool,
+    /// Send the batches without asking for confirmation on existential deposit warnings.
+    #[structopt(long)]
+    yes: bool,
+}
+
+/// A single payout of the CSV file.
+#[derive(Debug)]
+struct Payout {
+    dest: AccountId,
+    value: Balance,
+}
+
+/// Parses the `account,amount` rows, the first row is skipped if it is a header.
+fn parse_payouts(token: &Token, lines: Vec<String>) -> Result<Vec<Payout>> {
+    let parse_row = |line: &str| -> Result<Payout> {
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        match fields.as_slice() {
+            [account, amount] => Ok(Payout {
+                dest: parse_account(account)?,
+                value: token.to_balance(&amount.parse::<Amount>()?)?,
+            }),
+            _ => Err(anyhow!("expected `account,amount`")),
+        }
+    };
+
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(index, line)| !(*index == 0 && line.to_lowercase().starts_with("account")))
+        .map(|(_, line)| {
+            parse_row(line).map_err(|err| anyhow!("Invalid payout row `{}`: {}", line, err))
+        })
+        .collect()
+}
+
+/// Returns the weight, the byte length and the fee of a `batch_all` extrinsic of `calls`.
+async fn batch_info(
+    client: &CanyonClient,
+    signer: &CanyonSigner,
+    calls: &[Encoded],
+) -> Result<(u64, usize, Balance)> {
+    let batch_call = BatchAllCall::<CanyonRuntime> {
+        calls,
+        _runtime: PhantomData,
+    };
+    let encoded = client.0.create_signed(batch_call, signer).await?.encode();
+    let info = client
+        .payment_query_info(encoded.clone().into(), None)
+        .await?;
+    Ok((info.weight, encoded.len(), info.partial_fee))
+}
+
+/// Splits `calls` into consecutive batches fitting in a normal extrinsic, returns the range
+/// of each batch along with its estimated fee.
+async fn split_batches(
+    client: &CanyonClient,
+    signer: &CanyonSigner,
+    calls: &[Encoded],
+) -> Result<Vec<(Range<usize>, Balance)>> {
+    let max_weight = client.max_extrinsic_weight()?;
+    let max_length = client.max_extrinsic_length()? as usize;
+    let fits =
+        |(weight, length, _): (u64, usize, Balance)| weight <= max_weight && length <= max_length;
+
+    let mut batches = Vec::new();
+    let mut start = 0;
+    while start < calls.len() {
+        let mut end = start + 1;
+        let mut info = batch_info(client, signer, &calls[start..end]).await?;
+        if !fits(info) {
+            return Err(anyhow!(
+                "Payout #{} alone exceeds the extrinsic limits",
+                start + 1
+            ));
+        }
+        // Grow the batch one payout at a time as long as it still fits.
+        while end < calls.len() {
+            let next = batch_info(client, signer, &calls[start..end + 1]).await?;
+            if !fits(next) {
+                break;
+            }
+            info = next;
+            end += 1;
+        }
+        batches.push((start..end, info.2));
+        start = end;
+    }
+
+    Ok(batches)
+}
+
+/// Groups the names of the events of a batch by payout, the group of each payout ends with
+/// its `Balances::Transfer` event.
+fn payout_events(events: &[RawEvent]) -> Vec<Vec<String>> {
+    let mut groups = Vec::new();
+    let mut group = Vec::new();
+    for event in events {
+        let name = format!("{}::{}", event.module, event.variant);
+        match (event.module.as_str(), event.variant.as_str()) {
+            ("Balances", "Transfer") => {
+                group.push(name);
+                groups.push(std::mem::take(&mut group));
+            }
+            // The withdrawal of the fee is not part of any payout.
+            ("Balances", "Withdraw") => {}
+            ("Balances", _) | ("System", "NewAccount") => group.push(name),
+            _ => {}
+        }
+    }
+    groups
+}
+
+impl PayBatch {
+    pub async fn run(self, client: &CanyonClient, signer: &CanyonSigner) -> Result<()> {
+        let token = client.token();
+        let ed = client.existential_deposit()?;
+        let who = signer.account_id();
+
+        // Checked early so that nothing is sent if the receipt can not be written.
+        if !self.overwrite && self.receipt.exists() {
+            return Err(anyhow!(
+                "Receipt {} already exists, pass --overwrite to replace it",
+                self.receipt.display()
+            ));
+        }
+
+        let payouts = parse_payouts(&token, read_lines(&self.csv)?)?;
+        if payouts.is_empty() {
+            return Err(anyhow!("No payouts found in {}", self.csv.display()));
+        }
+        // Neither emits a `Transfer` event, which would shift the events of later payouts.
+        if let Some(payout) = payouts
+            .iter()
+            .find(|payout| payout.value == 0 || &payout.dest == who)
+        {
+            return Err(anyhow!(
+                "Invalid payout of {} to {}, zero amounts and payouts to the signer are not allowed",
+                token.display(payout.value),
+                payout.dest
+            ));
+        }
+
+        let calls = payouts
+            .iter()
+            .map(|payout| {
+                let dest: Address = payout.dest.clone().into();
+                client.0.encode(TransferCall::<CanyonRuntime> {
+                    to: &dest,
+                    amount: payout.value,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let batches = split_batches(client, signer, &calls).await?;
+        let total = checked_sum(payouts.iter().map(|payout| payout.value))?;
+        let fees = checked_sum(batches.iter().map(|(_, fee)| *fee))?;
+
+        println!(
+            "{} payouts, {} in total, in {} batches with estimated fees of {}",
+            payouts.len(),
+            token.display(total),
+            batches.len(),
+            token.display(fees)
+        );
+
+        let free = client.0.account(who, None).await?.data.free;
+        let mut warnings = vec![sender_warning(
+            &token,
+            who,
+            free,
+            checked_sum([total, fees])?,
+            ed,
+            false,
+        )?];
+        for payout in &payouts {
+            warnings.push(receiver_warning(client, &token, &payout.dest, payout.value, ed).await?);
+        }
+        if !proceed(warnings, self.yes)? {
+            return Ok(());
+        }
+
+        let mut receipt = if self.overwrite {
+            OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&self.receipt)?
+        } else {
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&self.receipt)?
+        };
+        writeln!(
+            receipt,
+            "{}",
+            csv_record(&[
+                "account",
+                "amount",
+                "block_number",
+                "block_hash",
+                "extrinsic_index",
+                "extrinsic_hash",
+                "events"
+            ])
+        )?;
+
+        for (index, (range, _)) in batches.into_iter().enumerate() {
+            let result = client
+                .0
+                .batch_all_and_watch(signer, &calls[range.clone()])
+                .await?;
+            let block_number = client
+                .block_number(result.block)
+                .await?
+                .map(|number| number.to_string())
+                .unwrap_or_default();
+            let extrinsic_index = client
+                .extrinsic_index(result.block, result.extrinsic)
+                .await?
+                .map(|index| index.to_string())
+                .unwrap_or_default();
+
+            let events = payout_events(&result.events);
+            for (offset, payout) in payouts[range.clone()].iter().enumerate() {
+                let events = events
+                    .get(offset)
+                    .map(|events| events.join(" "))
+                    .unwrap_or_default();
+                writeln!(
+                    receipt,
+                    "{}",
+                    csv_record(&[
+                        payout.dest.to_string(),
+                        token.display(payout.value),
+                        block_number.clone(),
+                        format!("{:?}", result.block),
+                        extrinsic_index.clone(),
+                        format!("{:?}", result.extrinsic),
+                        events,
+                    ])
+                )?;
+            }
+            receipt.flush()?;
+
+            println!(
+                "Batch {} of {} payouts included in block #{} {:?}",
+                index + 1,
+                range.len(),
+                block_number,
+                result.block
+            );
+        }
+
+        println!("Receipt written to {}", self.receipt.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+use subxt::{balances::LocksStoreExt, system::AccountStoreExt};
+
+use crate::{
+    client::CanyonClient,
+    runtime::primitives::{AccountId, BlockNumber},
+    utils::{parse_account, read_lines, OutputFormat},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Show {
+    /// Accounts to show the balances of.
+    #[structopt(index = 1, parse(try_from_str = parse_account))]
+    accounts: Vec<AccountId>,
+    /// File of accounts, one per line.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    file: Option<PathBuf>,
+    /// Specify the block number for retrieving the state.
+    #[structopt(long)]
+    block_number: Option<BlockNumber>,
+    /// Output format.
+    #[structopt(
+        long,
+        possible_values = &OutputFormat::variants(),
+        case_insensitive = true,
+        default_value = "table"
+    )]
+    format: OutputFormat,
+}
+
+/// Returns the lock identifier as ASCII, or in hex if it is not printable.
+pub fn display_lock_id(id: &[u8]) -> String {
+    let trimmed = id
+        .iter()
+        .rposition(|b| *b != 0)
+        .map_or(&id[..0], |last| &id[..=last]);
+    if trimmed.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        String::from_utf8_lossy(trimmed).into()
+    } else {
+        format!("0x{}", hex::encode(id))
+    }
+}
+
+impl Show {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let mut accounts = self.accounts;
+        if let Some(file) = self.file {
+            for line in read_lines(file)? {
+                accounts.push(parse_account(&line)?);
+            }
+        }
+        if accounts.is_empty() {
+            return Err(anyhow!(
+                "No accounts given, pass them as arguments or --file"
+            ));
+        }
+
+        let token = client.token();
+        let at = client.block_hash(self.block_number).await?;
+
+        let mut rows = Vec::with_capacity(accounts.len());
+        for who in accounts {
+            let data = client.0.account(&who, at).await?.data;
+            let locks = client
+                .0
+                .locks(&who, at)
+                .await?
+                .into_iter()
+                .map(|lock| {
+                    format!(
+                        "{}: {} ({:?})",
+                        display_lock_id(&lock.id),
+                        token.display(lock.amount),
+                        lock.reasons
+                    )
+                })
+                .collect::<Vec<_>>();
+            let transferable = data
+                .free
+                .saturating_sub(data.misc_frozen.max(data.fee_frozen));
+
+            rows.push(vec![
+                who.to_string(),
+                token.display(data.free),
+                token.display(data.reserved),
+                token.display(data.misc_frozen),
+                token.display(data.fee_frozen),
+                token.display(transferable),
+                locks.join("; "),
+            ]);
+        }
+
+        self.format.print(
+            &[
+                "account",
+                "free",
+                "reserved",
+                "misc frozen",
+                "fee frozen",
+                "transferable",
+                "locks",
+            ],
+            &rows,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,356 @@
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+use subxt::{
+    balances::{TransferCall, TransferCallExt, TransferEventExt},
+    sudo::SudoCallExt,
+    system::AccountStoreExt,
+    ExtrinsicSuccess, Signer,
+};
+
+use crate::{
+    client::CanyonClient,
+    pallets::balances::{
+        BalanceSetEventExt, ForceTransferCall, SetBalanceCall, TransferAllCall, TransferAllCallExt,
+        TransferKeepAliveCallExt,
+    },
+    runtime::{
+        primitives::{AccountId, Address, Balance},
+        CanyonRuntime, CanyonSigner,
+    },
+    token::{Amount, Token},
+    utils::{confirm, parse_account},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Transfer {
+    /// receiver
+    #[structopt(index = 1, long, parse(try_from_str = parse_account))]
+    dest: AccountId,
+    /// Amount to transfer, e.g. `1.5`, `1.5 CAN` or `150m`, in plancks with `--raw`.
+    #[structopt(index = 2)]
+    value: Amount,
+    /// Send the transfer without asking for confirmation on existential deposit warnings.
+    #[structopt(long)]
+    yes: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct TransferAll {
+    /// receiver
+    #[structopt(index = 1, long, parse(try_from_str = parse_account))]
+    dest: AccountId,
+    /// Leave the existential deposit in the signer account to keep it alive.
+    #[structopt(long)]
+    keep_alive: bool,
+    /// Send the transfer without asking for confirmation on existential deposit warnings.
+    #[structopt(long)]
+    yes: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ForceTransfer {
+    /// sender
+    #[structopt(index = 1, long, parse(try_from_str = parse_account))]
+    source: AccountId,
+    /// receiver
+    #[structopt(index = 2, long, parse(try_from_str = parse_account))]
+    dest: AccountId,
+    /// Amount to transfer, e.g. `1.5`, `1.5 CAN` or `150m`, in plancks with `--raw`.
+    #[structopt(index = 3)]
+    value: Amount,
+    /// Send the transfer without asking for confirmation on existential deposit warnings.
+    #[structopt(long)]
+    yes: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SetBalance {
+    /// Account to set the balances of.
+    #[structopt(index = 1, long, parse(try_from_str = parse_account))]
+    who: AccountId,
+    /// New free balance, e.g. `1.5`, `1.5 CAN` or `150m`, in plancks with `--raw`.
+    #[structopt(index = 2)]
+    new_free: Amount,
+    /// New reserved balance.
+    #[structopt(long, default_value = "0")]
+    new_reserved: Amount,
+    /// Send the call without asking for confirmation on existential deposit warnings.
+    #[structopt(long)]
+    yes: bool,
+}
+
+/// Returns the sum of `balances`, fails if it overflows the balance type.
+pub fn checked_sum<I: IntoIterator<Item = Balance>>(balances: I) -> Result<Balance> {
+    balances
+        .into_iter()
+        .try_fold(0, |sum: Balance, balance| sum.checked_add(balance))
+        .ok_or_else(|| anyhow!("Total amount overflows the balance type"))
+}
+
+/// Returns the warning if `who` spending `spent` out of its `free` balance would end up
+/// below the existential deposit `ed`, fails if `free` is insufficient.
+pub fn sender_warning(
+    token: &Token,
+    who: &AccountId,
+    free: Balance,
+    spent: Balance,
+    ed: Balance,
+    keep_alive: bool,
+) -> Result<Option<String>> {
+    let remaining = free.checked_sub(spent).ok_or_else(|| {
+        anyhow!(
+            "Insufficient free balance of {}: {}, {} required",
+            who,
+            token.display(free),
+            token.display(spent)
+        )
+    })?;
+
+    let warning = if remaining >= ed {
+        None
+    } else if keep_alive {
+        Some(format!(
+            "{} would be left with {}, below the existential deposit {}, the transfer will fail",
+            who,
+            token.display(remaining),
+            token.display(ed)
+        ))
+    } else if remaining == 0 {
+        Some(format!("{} would be reaped", who))
+    } else {
+        Some(format!(
+            "{} would be reaped and its remaining {}, below the existential deposit {}, lost",
+            who,
+            token.display(remaining),
+            token.display(ed)
+        ))
+    };
+
+    Ok(warning)
+}
+
+/// Returns the warning if `dest` does not exist and receiving `value` would make it a dust account.
+pub async fn receiver_warning(
+    client: &CanyonClient,
+    token: &Token,
+    dest: &AccountId,
+    value: Balance,
+    ed: Balance,
+) -> Result<Option<String>> {
+    let data = client.0.account(dest, None).await?.data;
+    if data.free + data.reserved == 0 && value < ed {
+        Ok(Some(format!(
+            "{} does not exist and {} is below the existential deposit {}, \
+            the chain refuses to create such a dust account",
+            dest,
+            token.display(value),
+            token.display(ed)
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Prints `warnings` and returns whether to proceed, asking for confirmation unless `yes`.
+pub fn proceed(warnings: Vec<Option<String>>, yes: bool) -> Result<bool> {
+    let warnings = warnings.into_iter().flatten().collect::<Vec<_>>();
+    for warning in &warnings {
+        println!("Warning: {}", warning);
+    }
+    if warnings.is_empty() || yes {
+        Ok(true)
+    } else {
+        confirm("Send the transaction anyway?")
+    }
+}
+
+fn print_transfer(token: &Token, result: &ExtrinsicSuccess<CanyonRuntime>) -> Result<()> {
+    if let Some(event) = result.transfer()? {
+        println!(
+            "Balance transfer success: {} from {} to {} in block {:?}",
+            token.display(event.amount),
+            event.from,
+            event.to,
+            result.block
+        );
+    } else {
+        println!("Failed to find Balances::Transfer Event");
+    }
+    Ok(())
+}
+
+impl Transfer {
+    pub async fn run(
+        self,
+        client: &CanyonClient,
+        signer: &CanyonSigner,
+        keep_alive: bool,
+    ) -> Result<()> {
+        let token = client.token();
+        let ed = client.existential_deposit()?;
+        let value = token.to_balance(&self.value)?;
+        let dest: Address = self.dest.clone().into();
+
+        let who = signer.account_id();
+        let free = client.0.account(who, None).await?.data.free;
+        let fee = client
+            .estimate_fee(
+                TransferCall::<CanyonRuntime> {
+                    to: &dest,
+                    amount: value,
+                },
+                signer,
+            )
+            .await?;
+
+        let warnings = vec![
+            sender_warning(
+                &token,
+                who,
+                free,
+                checked_sum([value, fee])?,
+                ed,
+                keep_alive,
+            )?,
+            receiver_warning(client, &token, &self.dest, value, ed).await?,
+        ];
+        if !proceed(warnings, self.yes)? {
+            return Ok(());
+        }
+
+        let result = if keep_alive {
+            client
+                .0
+                .transfer_keep_alive_and_watch(signer, &dest, value)
+                .await?
+        } else {
+            client.0.transfer_and_watch(signer, &dest, value).await?
+        };
+        print_transfer(&token, &result)
+    }
+}
+
+impl TransferAll {
+    pub async fn run(self, client: &CanyonClient, signer: &CanyonSigner) -> Result<()> {
+        let token = client.token();
+        let ed = client.existential_deposit()?;
+        let dest: Address = self.dest.clone().into();
+
+        let who = signer.account_id();
+        let free = client.0.account(who, None).await?.data.free;
+        let fee = client
+            .estimate_fee(
+                TransferAllCall::<CanyonRuntime> {
+                    to: &dest,
+                    keep_alive: self.keep_alive,
+                },
+                signer,
+            )
+            .await?;
+        // Locked balances are not transferable either, the actual amount may be lower.
+        let kept = if self.keep_alive { ed } else { 0 };
+        let value = free.saturating_sub(fee).saturating_sub(kept);
+
+        let warnings = vec![
+            sender_warning(
+                &token,
+                who,
+                free,
+                checked_sum([fee, value])?,
+                ed,
+                self.keep_alive,
+            )?,
+            receiver_warning(client, &token, &self.dest, value, ed).await?,
+        ];
+        if !proceed(warnings, self.yes)? {
+            return Ok(());
+        }
+
+        let result = client
+            .0
+            .transfer_all_and_watch(signer, &dest, self.keep_alive)
+            .await?;
+        print_transfer(&token, &result)
+    }
+}
+
+impl ForceTransfer {
+    pub async fn run(self, client: &CanyonClient, signer: &CanyonSigner) -> Result<()> {
+        let token = client.token();
+        let ed = client.existential_deposit()?;
+        let value = token.to_balance(&self.value)?;
+        let source: Address = self.source.clone().into();
+        let dest: Address = self.dest.clone().into();
+
+        // The fee is paid by the sudo key, not by the source account.
+        let free = client.0.account(&self.source, None).await?.data.free;
+        let warnings = vec![
+            sender_warning(&token, &self.source, free, value, ed, false)?,
+            receiver_warning(client, &token, &self.dest, value, ed).await?,
+        ];
+        if !proceed(warnings, self.yes)? {
+            return Ok(());
+        }
+
+        let call = client.0.encode(ForceTransferCall::<CanyonRuntime> {
+            source: &source,
+            dest: &dest,
+            amount: value,
+        })?;
+        let result = client.0.sudo_and_watch(signer, &call).await?;
+        print_transfer(&token, &result)
+    }
+}
+
+impl SetBalance {
+    pub async fn run(self, client: &CanyonClient, signer: &CanyonSigner) -> Result<()> {
+        let token = client.token();
+        let ed = client.existential_deposit()?;
+        let new_free = token.to_balance(&self.new_free)?;
+        let new_reserved = token.to_balance(&self.new_reserved)?;
+        let who: Address = self.who.clone().into();
+
+        // Balances below the existential deposit are set to zero by the chain.
+        let dust = |balance: Balance| balance > 0 && balance < ed;
+        let mut warnings = Vec::new();
+        if dust(new_free) {
+            warnings.push(Some(format!(
+                "free balance {} is below the existential deposit {} and would be set to zero",
+                token.display(new_free),
+                token.display(ed)
+            )));
+        }
+        if dust(new_reserved) {
+            warnings.push(Some(format!(
+                "reserved balance {} is below the existential deposit {} and would be set to zero",
+                token.display(new_reserved),
+                token.display(ed)
+            )));
+        }
+        if new_free < ed && new_reserved < ed {
+            warnings.push(Some(format!("{} would be reaped", self.who)));
+        }
+        if !proceed(warnings, self.yes)? {
+            return Ok(());
+        }
+
+        let call = client.0.encode(SetBalanceCall::<CanyonRuntime> {
+            who: &who,
+            new_free,
+            new_reserved,
+        })?;
+        let result = client.0.sudo_and_watch(signer, &call).await?;
+        if let Some(event) = result.balance_set()? {
+            println!(
+                "Balances of {} set: free {}, reserved {}",
+                event.who,
+                token.display(event.free),
+                token.display(event.reserved)
+            );
+        } else {
+            println!("Failed to find Balances::BalanceSet Event");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use codec::Decode;
+use structopt::StructOpt;
+use subxt::{system::Phase, Raw, RawEvent};
+
+use crate::{
+    client::CanyonClient,
+    hook::HookParams,
+    runtime::primitives::{AccountId, Balance, BlockNumber},
+    token::Token,
+    utils::{parse_account, subscribe_with_backoff, write_atomically, Resubscribe},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Watch {
+    /// Accounts to watch the transfers of.
+    #[structopt(index = 1, required = true, parse(try_from_str = parse_account))]
+    accounts: Vec<AccountId>,
+    /// File storing the last seen finalized block, watching resumes after it on restart.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    state: Option<PathBuf>,
+    /// Print the transfers as JSON lines.
+    #[structopt(long)]
+    json: bool,
+    #[structopt(flatten)]
+    hook: HookParams,
+}
+
+/// A balances event involving a watched account.
+#[derive(Debug)]
+struct TransferEvent {
+    variant: String,
+    from: Option<AccountId>,
+    to: AccountId,
+    amount: Balance,
+}
+
+/// Decodes the balances events moving funds to or from one of `accounts`.
+fn decode_event(event: &RawEvent, accounts: &HashSet<AccountId>) -> Result<Option<TransferEvent>> {
+    if event.module != "Balances" {
+        return Ok(None);
+    }
+
+    let data = &mut event.data.as_slice();
+    let (from, to, amount) = match event.variant.as_str() {
+        "Transfer" => {
+            let (from, to, amount) = <(AccountId, AccountId, Balance)>::decode(data)?;
+            (Some(from), to, amount)
+        }
+        "ReserveRepatriated" => {
+            let (from, to, amount, _status) = <(AccountId, AccountId, Balance, u8)>::decode(data)?;
+            (Some(from), to, amount)
+        }
+        // `Endowed` is not watched, it always comes along with a `Transfer` or a `Deposit`.
+        "Deposit" => {
+            let (to, amount) = <(AccountId, Balance)>::decode(data)?;
+            (None, to, amount)
+        }
+        _ => return Ok(None),
+    };
+
+    let involved =
+        accounts.contains(&to) || from.as_ref().map_or(false, |from| accounts.contains(from));
+    Ok(if involved {
+        Some(TransferEvent {
+            variant: event.variant.clone(),
+            from,
+            to,
+            amount,
+        })
+    } else {
+        None
+    })
+}
+
+/// Watcher of the transfers of some accounts in the finalized blocks.
+struct TransferWatcher {
+    accounts: HashSet<AccountId>,
+    state: Option<PathBuf>,
+    json: bool,
+    hook: HookParams,
+    /// The last finalized block we have processed.
+    last_seen: Option<BlockNumber>,
+}
+
+impl TransferWatcher {
+    /// Returns the last seen block stored in the state file, if any.
+    fn load_last_seen(&self) -> Result<Option<BlockNumber>> {
+        match self.state {
+            Some(ref path) if path.exists() => {
+                let content = std::fs::read_to_string(path)?;
+                let number = content
+                    .trim()
+                    .parse()
+                    .map_err(|err| anyhow!("Invalid state file {}: {:?}", path.display(), err))?;
+                Ok(Some(number))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Stores the last seen block in the state file.
+    fn store_last_seen(&self, number: BlockNumber) -> Result<()> {
+        match self.state {
+            Some(ref path) => write_atomically(path, number.to_string().as_bytes()),
+            None => Ok(()),
+        }
+    }
+
+    /// Handles the events of the finalized block `number`.
+    ///
+    /// Fails if the block can not be fetched, a block whose events can not be decoded
+    /// is reported and skipped since retrying it would not help.
+    async fn on_block(
+        &self,
+        client: &CanyonClient,
+        token: &Token,
+        number: BlockNumber,
+    ) -> Result<()> {
+        let block = client
+            .block_hash(Some(number))
+            .await?
+            .ok_or_else(|| anyhow!("Block #{} not found", number))?;
+        let data = client.events_data(block).await?;
+        let events = match client.decode_events(&data) {
+            Ok(events) => events,
+            Err(err) => {
+                eprintln!("Skipping block #{}, undecodable events: {:?}", number, err);
+                return Ok(());
+            }
+        };
+
+        for (phase, raw) in events {
+            let event = match raw {
+                Raw::Event(event) => event,
+                Raw::Error(_) => continue,
+            };
+            let transfer = match decode_event(&event, &self.accounts) {
+                Ok(Some(transfer)) => transfer,
+                Ok(None) => continue,
+                Err(err) => {
+                    eprintln!(
+                        "block #{}: unable to decode Balances::{}: {:?}",
+                        number, event.variant, err
+                    );
+                    continue;
+                }
+            };
+            let extrinsic_index = match phase {
+                Phase::ApplyExtrinsic(index) => Some(index),
+                _ => None,
+            };
+            let direction = if self.accounts.contains(&transfer.to) {
+                "incoming"
+            } else {
+                "outgoing"
+            };
+            let from = transfer.from.as_ref().map(ToString::to_string);
+
+            let payload = serde_json::json!({
+                "block": number,
+                "blockHash": format!("{:?}", block),
+                "extrinsicIndex": extrinsic_index,
+                "event": format!("Balances::{}", transfer.variant),
+                "direction": direction,
+                "from": from,
+                "to": transfer.to.to_string(),
+                "amount": transfer.amount.to_string(),
+                "displayAmount": token.display(transfer.amount),
+            });
+
+            if self.json {
+                println!("{}", payload);
+            } else {
+                println!(
+                    "block #{}: {} {} {} from {} to {}",
+                    number,
+                    direction,
+                    event.variant,
+                    token.display(transfer.amount),
+                    from.as_deref().unwrap_or("-"),
+                    transfer.to
+                );
+            }
+            self.hook.notify(&payload).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Resubscribe for TransferWatcher {
+    /// Subscribes to the finalized blocks until the subscription fails or ends.
+    async fn run_subscription(&mut self, url: &str, connected: &mut bool) -> Result<()> {
+        let client = CanyonClient::create(url).await?;
+        let token = client.token();
+        let mut subscription = client.0.subscribe_finalized_blocks().await?;
+        *connected = true;
+
+        loop {
+            let header = match subscription.next().await {
+                Ok(Some(header)) => header,
+                Ok(None) => return Err(anyhow!("Subscription closed")),
+                Err(err) => return Err(err.into()),
+            };
+
+            // The finalized blocks are not always notified one by one, the blocks since
+            // the last seen one, possibly from a previous run, have to be queried.
+            // A block which can not be fetched interrupts the subscription before being
+            // marked as seen, so that it is retried once reconnected.
+            let from = self.last_seen.map_or(header.number, |last| last + 1);
+            for number in from..=header.number {
+                self.on_block(&client, &token, number).await?;
+                self.last_seen = Some(number);
+                if let Err(err) = self.store_last_seen(number) {
+                    eprintln!("Failed to store the last seen block #{}: {:?}", number, err);
+                }
+            }
+        }
+    }
+}
+
+impl Watch {
+    /// Watches the transfers of the accounts, reconnecting with an exponential backoff if
+    /// the connection is lost.
+    pub async fn run(self, url: &str) -> Result<()> {
+        let mut watcher = TransferWatcher {
+            accounts: self.accounts.into_iter().collect(),
+            state: self.state,
+            json: self.json,
+            hook: self.hook,
+            last_seen: None,
+        };
+        watcher.last_seen = watcher.load_last_seen()?;
+        if let Some(last_seen) = watcher.last_seen {
+            eprintln!("Resuming after block #{}", last_seen);
+        }
+
+        subscribe_with_backoff(url, "transfers", &mut watcher).await
+    }
+}
@@ -0,0 +1,163 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+
+use crate::{
+    client::CanyonClient,
+    pallets::permastore::StoreCallExt,
+    runtime::{
+        primitives::{BlockNumber, Hash},
+        CanyonSigner,
+    },
+    utils::{compute_chunk_root, csv_record, parse_hash, read_lines},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Migrate {
+    /// The websocket url of the node to fetch the data from, defaults to `--url`.
+    #[structopt(long, value_name = "URL")]
+    source_url: Option<String>,
+    /// The websocket url of the node to store the data to.
+    #[structopt(long, value_name = "URL")]
+    dest_url: String,
+    /// File of the chunk roots to migrate.
+    ///
+    /// Each line is either a chunk root or a `block,extrinsic,chunk_root` record,
+    /// e.g. the `index.csv` written by `permastore mirror`.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    roots: PathBuf,
+    /// Write the mapping report to this file instead of the standard output.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    report: Option<PathBuf>,
+}
+
+/// Inclusion location of a `store` extrinsic.
+type Location = (BlockNumber, u32);
+
+/// Parses a line of the roots file.
+fn parse_root_line(line: &str) -> Result<(Option<Location>, Hash)> {
+    let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+    match fields.as_slice() {
+        [chunk_root] => Ok((None, parse_hash(chunk_root)?)),
+        [block, extrinsic, chunk_root, ..] => Ok((
+            Some((block.parse()?, extrinsic.parse()?)),
+            parse_hash(chunk_root)?,
+        )),
+        _ => Err(anyhow!("Invalid line in roots file: {}", line)),
+    }
+}
+
+fn display_location(location: Option<Location>) -> [String; 2] {
+    match location {
+        Some((number, index)) => [number.to_string(), index.to_string()],
+        None => [String::new(), String::new()],
+    }
+}
+
+impl Migrate {
+    pub async fn run(self, client: &CanyonClient, signer: &CanyonSigner) -> Result<()> {
+        let items = read_lines(&self.roots)?
+            .iter()
+            // Skip the header of `index.csv`.
+            .filter(|line| !line.starts_with("block,"))
+            .map(|line| parse_root_line(line))
+            .collect::<Result<Vec<_>>>()?;
+
+        let source = match self.source_url {
+            Some(url) => CanyonClient::create(url).await?,
+            None => client.clone(),
+        };
+        let dest = CanyonClient::create(self.dest_url).await?;
+
+        // The chunk roots can be reused only if both networks chunk the data in the same way.
+        let chunk_size = source.chunk_size();
+        if chunk_size != dest.chunk_size() {
+            return Err(anyhow!(
+                "Chunk size of source ({}) differs from the one of dest ({})",
+                chunk_size,
+                dest.chunk_size()
+            ));
+        }
+
+        let mut report = vec![csv_record(&[
+            "chunk_root",
+            "data_size",
+            "old_block",
+            "old_extrinsic",
+            "new_block",
+            "new_extrinsic",
+            "status",
+        ])];
+
+        for (old_location, chunk_root) in items {
+            let (data_size, new_location, status) =
+                match migrate_one(&source, &dest, signer, chunk_root, chunk_size).await {
+                    Ok((data_size, new_location)) => {
+                        (data_size.to_string(), Some(new_location), "ok".to_string())
+                    }
+                    Err(err) => (String::new(), None, format!("{}", err)),
+                };
+            println!("{:?}: {}", chunk_root, status);
+
+            let [old_block, old_extrinsic] = display_location(old_location);
+            let [new_block, new_extrinsic] = display_location(new_location);
+            report.push(csv_record(&[
+                format!("{:?}", chunk_root),
+                data_size,
+                old_block,
+                old_extrinsic,
+                new_block,
+                new_extrinsic,
+                status,
+            ]));
+        }
+
+        let report = report.join("\n") + "\n";
+        match self.report {
+            Some(path) => fs::write(path, report)?,
+            None => print!("{}", report),
+        }
+
+        Ok(())
+    }
+}
+
+/// Copies the data of `chunk_root` from `source` to `dest`, returns the data size and
+/// the inclusion location on `dest`.
+async fn migrate_one(
+    source: &CanyonClient,
+    dest: &CanyonClient,
+    signer: &CanyonSigner,
+    chunk_root: Hash,
+    chunk_size: u32,
+) -> Result<(u32, Location)> {
+    let data = source
+        .permastore_retrieve_data(chunk_root)
+        .await?
+        .ok_or_else(|| anyhow!("data not found on source"))?
+        .0;
+
+    let computed = compute_chunk_root(&data, chunk_size);
+    if computed != chunk_root {
+        return Err(anyhow!("chunk root mismatch, got {:?}", computed));
+    }
+    let data_size = data.len() as u32;
+
+    dest.permastore_submit(data.into()).await?;
+    let result = dest
+        .0
+        .store_and_watch(signer, data_size, chunk_root)
+        .await?;
+
+    let number = dest
+        .block_number(result.block)
+        .await?
+        .ok_or_else(|| anyhow!("block {:?} not found on dest", result.block))?;
+    let index = dest
+        .extrinsic_index(result.block, result.extrinsic)
+        .await?
+        .ok_or_else(|| anyhow!("extrinsic {:?} not found on dest", result.extrinsic))?;
+
+    Ok((data_size, (number, index)))
+}
@@ -0,0 +1,212 @@
+use std::{
+    collections::HashSet,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+
+use crate::{
+    client::{CanyonClient, StoreTransaction},
+    runtime::primitives::{BlockNumber, Hash},
+    utils::{compute_chunk_root, csv_record, write_atomically},
+};
+
+/// File recording the last block mirrored completely.
+const PROGRESS_FILE: &str = "progress";
+
+/// File recording where each mirrored data comes from.
+const INDEX_FILE: &str = "index.csv";
+
+/// Directory of the data files named after their chunk roots.
+const DATA_DIR: &str = "data";
+
+#[derive(Debug, StructOpt)]
+pub struct Mirror {
+    /// First block of the range.
+    #[structopt(long)]
+    from: BlockNumber,
+    /// Last block of the range, defaults to the best block.
+    #[structopt(long)]
+    to: Option<BlockNumber>,
+    /// Directory of the local chunk store.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    dir: PathBuf,
+}
+
+/// Content-addressed store of the transaction data on disk.
+///
+/// The data of chunk root `0xabcd..` is located at `data/ab/abcd..`, `index.csv`
+/// maps each data to the extrinsic storing it.
+struct ChunkStore {
+    dir: PathBuf,
+    indexed: HashSet<(BlockNumber, u32)>,
+}
+
+impl ChunkStore {
+    fn open(dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(dir.join(DATA_DIR))?;
+
+        let index_path = dir.join(INDEX_FILE);
+        let mut indexed = HashSet::new();
+        if index_path.exists() {
+            for line in fs::read_to_string(&index_path)?.lines().skip(1) {
+                let mut fields = line.split(',');
+                if let (Some(Ok(number)), Some(Ok(index))) =
+                    (fields.next().map(str::parse), fields.next().map(str::parse))
+                {
+                    indexed.insert((number, index));
+                }
+            }
+        } else {
+            fs::write(
+                &index_path,
+                csv_record(&["block", "extrinsic", "chunk_root", "data_size"]) + "\n",
+            )?;
+        }
+
+        Ok(Self { dir, indexed })
+    }
+
+    fn last_mirrored(&self) -> Result<Option<BlockNumber>> {
+        let path = self.dir.join(PROGRESS_FILE);
+        if path.exists() {
+            Ok(Some(fs::read_to_string(path)?.trim().parse()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn set_last_mirrored(&self, number: BlockNumber) -> Result<()> {
+        write_atomically(&self.dir.join(PROGRESS_FILE), number.to_string().as_bytes())
+    }
+
+    fn data_path(&self, chunk_root: &Hash) -> PathBuf {
+        data_path(&self.dir, chunk_root)
+    }
+
+    fn contains(&self, chunk_root: &Hash) -> bool {
+        self.data_path(chunk_root).exists()
+    }
+
+    fn is_indexed(&self, tx: &StoreTransaction) -> bool {
+        self.indexed
+            .contains(&(tx.block_number, tx.extrinsic_index))
+    }
+
+    fn write_data(&self, chunk_root: &Hash, data: &[u8]) -> Result<()> {
+        let path = self.data_path(chunk_root);
+        fs::create_dir_all(path.parent().expect("data path has a parent; qed"))?;
+        write_atomically(&path, data)
+    }
+
+    fn index(&mut self, tx: &StoreTransaction) -> Result<()> {
+        let mut index = OpenOptions::new()
+            .append(true)
+            .open(self.dir.join(INDEX_FILE))?;
+        writeln!(
+            index,
+            "{}",
+            csv_record(&[
+                tx.block_number.to_string(),
+                tx.extrinsic_index.to_string(),
+                format!("{:?}", tx.chunk_root),
+                tx.data_size.to_string(),
+            ])
+        )?;
+        self.indexed.insert((tx.block_number, tx.extrinsic_index));
+
+        Ok(())
+    }
+}
+
+/// Returns the path of the data of `chunk_root` in the chunk store `dir`.
+pub fn data_path(dir: &Path, chunk_root: &Hash) -> PathBuf {
+    let hex_root = hex::encode(chunk_root);
+    dir.join(DATA_DIR).join(&hex_root[..2]).join(hex_root)
+}
+
+impl Mirror {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let to = match self.to {
+            Some(to) => to,
+            None => client.best_number().await?,
+        };
+
+        let chunk_size = client.chunk_size();
+        let mut store = ChunkStore::open(self.dir)?;
+
+        let last = store.last_mirrored()?;
+        let from = match last {
+            Some(last) if (self.from..=to).contains(&last) => {
+                println!("Resuming from block #{}", last + 1);
+                last + 1
+            }
+            _ => self.from,
+        };
+
+        // The progress only records blocks mirrored completely and contiguously, it is
+        // neither moved backwards nor across a gap, and stops at the first block with
+        // missing data so that the missing data is retried on the next run.
+        let mut advance = last.map_or(true, |last| from <= last + 1);
+        let (mut mirrored, mut missing) = (0, Vec::new());
+
+        for number in from..=to {
+            let mut complete = true;
+            for tx in client.store_transactions(number).await? {
+                if store.is_indexed(&tx) {
+                    continue;
+                }
+
+                if !store.contains(&tx.chunk_root) {
+                    let data = match client.permastore_retrieve_data(tx.chunk_root).await? {
+                        Some(data) => data.0,
+                        None => {
+                            println!("block #{}: data of {:?} not found", number, tx.chunk_root);
+                            missing.push(tx.chunk_root);
+                            complete = false;
+                            continue;
+                        }
+                    };
+
+                    let chunk_root = compute_chunk_root(&data, chunk_size);
+                    if chunk_root != tx.chunk_root || data.len() as u32 != tx.data_size {
+                        return Err(anyhow!(
+                            "Data of {:?} in block #{} does not match the chunk root, got {:?}",
+                            tx.chunk_root,
+                            number,
+                            chunk_root
+                        ));
+                    }
+
+                    store.write_data(&tx.chunk_root, &data)?;
+                }
+                store.index(&tx)?;
+
+                mirrored += 1;
+                println!(
+                    "block #{}: mirrored {:?}, {} bytes",
+                    number, tx.chunk_root, tx.data_size
+                );
+            }
+
+            advance &= complete;
+            if advance && last.map_or(true, |last| number > last) {
+                store.set_last_mirrored(number)?;
+            }
+        }
+
+        println!(
+            "Mirrored {} data items, {} missing",
+            mirrored,
+            missing.len()
+        );
+        for chunk_root in missing {
+            println!("  missing: {:?}", chunk_root);
+        }
+
+        Ok(())
+    }
+}
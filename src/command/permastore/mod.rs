@@ -0,0 +1,152 @@
+mod migrate;
+pub mod mirror;
+mod node;
+mod quote;
+mod remove;
+mod storage;
+mod usage;
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use sp_core::Encode;
+use structopt::StructOpt;
+
+use crate::client::CanyonClient;
+use crate::runtime::CanyonSigner;
+use crate::utils::compute_chunk_root;
+
+#[derive(Debug, StructOpt)]
+pub struct SharedParams {
+    /// Raw data to upload.
+    #[structopt(long, value_name = "DATA")]
+    data: Option<String>,
+
+    /// Absoluate path of the data file to upload.
+    #[structopt(long, value_name = "PATH", parse(from_os_str), conflicts_with = "data")]
+    path: Option<PathBuf>,
+
+    /// Proceed even if the chunk size of the chain differs from the local one.
+    #[structopt(long)]
+    allow_chunk_size_mismatch: bool,
+}
+
+impl SharedParams {
+    /// Returns the raw bytes of transaction data.
+    pub fn read_data(&self) -> Result<Vec<u8>> {
+        if let Some(ref data) = self.data {
+            Ok(data.as_bytes().to_vec())
+        } else if let Some(ref path) = self.path {
+            std::fs::read(path).map_err(Into::into)
+        } else {
+            Err(anyhow!(
+                "--data or --path is required, please rerun the command with `--help`."
+            ))
+        }
+    }
+}
+
+/// Permastore
+#[derive(Debug, StructOpt)]
+pub enum Permastore {
+    /// Submit the `store` extrinsic only.
+    Store {
+        #[structopt(flatten)]
+        shared: SharedParams,
+    },
+    /// Submit the transction data only.
+    Submit {
+        #[structopt(flatten)]
+        shared: SharedParams,
+        /// Prepare and display the data info but not send it.
+        #[structopt(long)]
+        dry_run: bool,
+    },
+    /// Submit the `store` extrinsic and the transaction data.
+    StoreWithData {
+        #[structopt(flatten)]
+        shared: SharedParams,
+        /// Prepare and display the data info but not send it.
+        #[structopt(long)]
+        dry_run: bool,
+    },
+    /// Remove data.
+    Remove(remove::Remove),
+    /// Inspect the permastore storage items.
+    Storage(storage::Storage),
+    /// Migrate the data from one network to another.
+    Migrate(migrate::Migrate),
+    /// Mirror the data stored in a range of blocks to a local directory.
+    Mirror(mirror::Mirror),
+    /// Manage the data stored on the connected node.
+    Node(node::Node),
+    /// Quote the fee of storing some bytes now and project it over the coming months.
+    Quote(quote::Quote),
+    /// Report the bytes stored per account over a range of blocks.
+    Usage(usage::Usage),
+}
+
+impl Permastore {
+    pub async fn run(self, url: String, signer: CanyonSigner) -> Result<()> {
+        // The chunk roots to remove are all validated before any RPC call.
+        if let Self::Remove(ref remove) = self {
+            let chunk_roots = remove.chunk_roots()?;
+            let client = CanyonClient::create(url).await?;
+            return remove.run(&client, chunk_roots).await;
+        }
+
+        let client = CanyonClient::create(url).await?;
+
+        let process_data = |data: &[u8], chunk_size: u32| {
+            (compute_chunk_root(data, chunk_size), data.len() as u32)
+        };
+
+        match self {
+            Self::Store { shared } => {
+                let chunk_size = client.checked_chunk_size(shared.allow_chunk_size_mismatch)?;
+                let raw_data = shared.read_data()?;
+                client.store(&signer, raw_data, chunk_size).await?;
+            }
+            Self::Submit { shared, dry_run } => {
+                let chunk_size = client.checked_chunk_size(shared.allow_chunk_size_mismatch)?;
+                let raw_data = shared.read_data()?;
+                if dry_run {
+                    let (chunk_root, data_size) = process_data(&raw_data, chunk_size);
+                    println!("data size in bytes: {:?}", data_size);
+                    println!("        chunk root: {:?}", chunk_root);
+                } else {
+                    let ret = client.permastore_submit(raw_data.into()).await?;
+                    println!("Submitted result: {:?}", ret);
+                }
+            }
+            Self::StoreWithData { shared, dry_run } => {
+                let chunk_size = client.checked_chunk_size(shared.allow_chunk_size_mismatch)?;
+                let raw_data = shared.read_data()?;
+
+                let (chunk_root, data_size) = process_data(&raw_data, chunk_size);
+                println!("data size in bytes: {:?}", data_size);
+                println!("        chunk root: {:?}", chunk_root);
+
+                if !dry_run {
+                    let store_call =
+                        crate::pallets::permastore::StoreCall::new(data_size, chunk_root);
+                    let uxt = client.0.create_signed(store_call, &signer).await?;
+
+                    let ret = client
+                        .permastore_submit_extrinsic(uxt.encode().into(), raw_data.into())
+                        .await?;
+                    println!("  Submitted result: {:?}", ret);
+                }
+            }
+            Self::Remove(_) => unreachable!("removal is handled before connecting; qed"),
+            Self::Migrate(migrate) => migrate.run(&client, &signer).await?,
+            Self::Mirror(mirror) => mirror.run(&client).await?,
+            Self::Node(node) => node.run(&client).await?,
+            Self::Quote(quote) => quote.run(&client, &signer).await?,
+            Self::Usage(usage) => usage.run(&client).await?,
+            Self::Storage(storage) => storage.run(&client).await?,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+
+use crate::{
+    client::{CanyonClient, StoredDataInfo},
+    runtime::primitives::{BlockNumber, Hash},
+    utils::{parse_hash, read_lines, OutputFormat},
+};
+
+/// Manage the transaction data stored on the connected node.
+#[derive(Debug, StructOpt)]
+pub enum Node {
+    /// List the chunk roots and sizes of the data stored on the node.
+    List {
+        /// Output format.
+        #[structopt(
+            long,
+            possible_values = &OutputFormat::variants(),
+            case_insensitive = true,
+            default_value = "table"
+        )]
+        format: OutputFormat,
+    },
+    /// Show the totals of the data stored on the node.
+    Stat,
+    /// Remove the data stored on the node in bulk.
+    Purge {
+        /// File of the chunk roots to remove, one per line.
+        #[structopt(long, value_name = "PATH", parse(from_os_str))]
+        file: Option<PathBuf>,
+        /// Remove the data stored more than this number of blocks ago.
+        #[structopt(long, value_name = "BLOCKS")]
+        older_than: Option<BlockNumber>,
+        /// Display the data to remove but not remove it.
+        #[structopt(long)]
+        dry_run: bool,
+    },
+}
+
+impl Node {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let stored = client.permastore_list_data().await?;
+
+        match self {
+            Self::List { format } => {
+                let rows = stored
+                    .iter()
+                    .map(|info| {
+                        vec![
+                            format!("{:?}", info.chunk_root),
+                            info.data_size.to_string(),
+                            info.block_number.map(|n| n.to_string()).unwrap_or_default(),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                format.print(&["chunk root", "bytes", "block"], &rows);
+            }
+            Self::Stat => {
+                let total_size: u64 = stored.iter().map(|info| info.data_size).sum();
+                let oldest = stored.iter().filter_map(|info| info.block_number).min();
+                let newest = stored.iter().filter_map(|info| info.block_number).max();
+                println!("    data items: {}", stored.len());
+                println!("total in bytes: {}", total_size);
+                if let (Some(oldest), Some(newest)) = (oldest, newest) {
+                    println!("   block range: #{} - #{}", oldest, newest);
+                }
+            }
+            Self::Purge {
+                file,
+                older_than,
+                dry_run,
+            } => {
+                let to_remove = select_purged(client, &stored, file, older_than).await?;
+
+                for info in &to_remove {
+                    if dry_run {
+                        println!(
+                            "Would remove {:?} ({} bytes)",
+                            info.chunk_root, info.data_size
+                        );
+                    } else {
+                        let ret = client.permastore_remove_data(info.chunk_root).await?;
+                        println!("Removed {:?}: {:?}", info.chunk_root, ret);
+                    }
+                }
+
+                let total_size: u64 = to_remove.iter().map(|info| info.data_size).sum();
+                println!(
+                    "{} {} data items, {} bytes in total",
+                    if dry_run { "Would remove" } else { "Removed" },
+                    to_remove.len(),
+                    total_size
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the stored data matching the purge criteria.
+async fn select_purged(
+    client: &CanyonClient,
+    stored: &[StoredDataInfo],
+    file: Option<PathBuf>,
+    older_than: Option<BlockNumber>,
+) -> Result<Vec<StoredDataInfo>> {
+    if file.is_none() && older_than.is_none() {
+        return Err(anyhow!(
+            "--file or --older-than is required, please rerun the command with `--help`."
+        ));
+    }
+
+    let roots = match file {
+        Some(path) => Some(
+            read_lines(path)?
+                .iter()
+                .map(|line| parse_hash(line))
+                .collect::<Result<HashSet<Hash>>>()?,
+        ),
+        None => None,
+    };
+
+    let before = match older_than {
+        Some(blocks) => Some(client.best_number().await?.saturating_sub(blocks)),
+        None => None,
+    };
+
+    Ok(stored
+        .iter()
+        .filter(|info| {
+            roots
+                .as_ref()
+                .map(|roots| roots.contains(&info.chunk_root))
+                .unwrap_or(true)
+        })
+        .filter(|info| match (before, info.block_number) {
+            (Some(before), Some(number)) => number < before,
+            // Keep the data of unknown age.
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .cloned()
+        .collect())
+}
@@ -0,0 +1,107 @@
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+
+use crate::{
+    client::CanyonClient,
+    pallets::permastore::{StoreCall, WeaveSizeStoreExt},
+    runtime::{primitives::BlockNumber, CanyonRuntime, CanyonSigner},
+};
+
+/// Expected block time in milliseconds used when the chain does not expose one.
+const DEFAULT_BLOCK_TIME: u64 = 6000;
+
+const MILLISECS_PER_MONTH: u64 = 30 * 24 * 60 * 60 * 1000;
+
+#[derive(Debug, StructOpt)]
+pub struct Quote {
+    /// Byte size of the data to store.
+    #[structopt(long)]
+    size: u32,
+    /// Number of months to project.
+    #[structopt(long, default_value = "12")]
+    months: u32,
+    /// Yearly decline rate of the storage cost, e.g. 0.3 means 30% cheaper every year.
+    #[structopt(long, default_value = "0.3")]
+    decline_rate: f64,
+    /// Number of recent blocks used to estimate the weave growth.
+    #[structopt(long, default_value = "14400")]
+    history: BlockNumber,
+}
+
+impl Quote {
+    pub async fn run(self, client: &CanyonClient, signer: &CanyonSigner) -> Result<()> {
+        if !(0.0..1.0).contains(&self.decline_rate) {
+            return Err(anyhow!(
+                "--decline-rate must be in [0, 1), got {}",
+                self.decline_rate
+            ));
+        }
+
+        // The chunk root does not affect the fee, only the encoded length does.
+        let store_call = StoreCall::<CanyonRuntime>::new(self.size, Default::default());
+        let fee = client.estimate_fee(store_call, signer).await?;
+
+        let best = client.best_number().await?;
+        let from = best.saturating_sub(self.history);
+        let weave_size_now = client
+            .0
+            .weave_size(client.block_hash(Some(best)).await?)
+            .await?;
+        let weave_size_then = client
+            .0
+            .weave_size(client.block_hash(Some(from)).await?)
+            .await?;
+        let growth_per_block = if best > from {
+            weave_size_now.saturating_sub(weave_size_then) as f64 / (best - from) as f64
+        } else {
+            0f64
+        };
+
+        let block_time = client
+            .constant::<u64>("Babe", "ExpectedBlockTime")
+            .unwrap_or(DEFAULT_BLOCK_TIME);
+        let blocks_per_month = (MILLISECS_PER_MONTH / block_time) as f64;
+
+        let token = client.token();
+        println!("data size in bytes: {}", self.size);
+        println!("  fee to store now: {}", token.display(fee));
+        // The fee includes the base fee, hence an average rather than a marginal cost.
+        println!(
+            "  avg fee per byte: {}",
+            token.display(fee / self.size.max(1) as u128)
+        );
+        println!("        weave size: {} at block #{}", weave_size_now, best);
+        println!(
+            "      weave growth: {:.2} bytes/block over the last {} blocks",
+            growth_per_block,
+            best - from
+        );
+        println!();
+        println!(
+            "{:>5}  {:>24}  {:>24}",
+            "month", "projected weave size", "projected fee"
+        );
+
+        // Every miner replicates the whole weave, the cost of storing a byte follows the
+        // weave size while the cost of the storage itself declines over time.
+        for month in 1..=self.months {
+            let weave_size =
+                weave_size_now as f64 + growth_per_block * blocks_per_month * month as f64;
+            let weave_factor = if weave_size_now > 0 {
+                weave_size / weave_size_now as f64
+            } else {
+                1f64
+            };
+            let decline_factor = (1f64 - self.decline_rate).powf(month as f64 / 12f64);
+            let projected_fee = fee as f64 * weave_factor * decline_factor;
+            println!(
+                "{:>5}  {:>24.0}  {:>24}",
+                month,
+                weave_size,
+                token.display(projected_fee as u128)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+
+use crate::{
+    client::CanyonClient,
+    runtime::primitives::Hash,
+    utils::{confirm, parse_hash, read_lines},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Remove {
+    /// Chunk roots of the data you want to delete.
+    #[structopt(index = 1, long = "chunk-root", parse(try_from_str = parse_hash))]
+    chunk_roots: Vec<Hash>,
+    /// File of the chunk roots to delete, one per line.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    file: Option<PathBuf>,
+    /// Remove the data without asking for confirmation.
+    #[structopt(long)]
+    yes: bool,
+}
+
+impl Remove {
+    /// Returns the deduplicated chunk roots given as arguments and in `--file`.
+    pub fn chunk_roots(&self) -> Result<Vec<Hash>> {
+        let mut chunk_roots = self.chunk_roots.clone();
+        if let Some(ref path) = self.file {
+            for line in read_lines(path)? {
+                chunk_roots.push(parse_hash(&line)?);
+            }
+        }
+        let mut seen = HashSet::new();
+        chunk_roots.retain(|chunk_root| seen.insert(*chunk_root));
+
+        if chunk_roots.is_empty() {
+            return Err(anyhow!(
+                "No chunk root is given, please rerun the command with `--help`."
+            ));
+        }
+
+        Ok(chunk_roots)
+    }
+
+    /// Removes the data of `chunk_roots` returned by `chunk_roots()`.
+    pub async fn run(&self, client: &CanyonClient, chunk_roots: Vec<Hash>) -> Result<()> {
+        if !self.yes {
+            println!("The data of following chunk roots will be removed:");
+            for chunk_root in &chunk_roots {
+                println!("  {:?}", chunk_root);
+            }
+            if !confirm(&format!("Remove {} data items?", chunk_roots.len()))? {
+                println!("Aborted");
+                return Ok(());
+            }
+        }
+
+        let (mut removed, mut not_found, mut failed) = (0, 0, 0);
+        for chunk_root in chunk_roots {
+            match client.permastore_remove_data(chunk_root).await {
+                Ok(true) => {
+                    removed += 1;
+                    println!("{:?}: removed", chunk_root);
+                }
+                Ok(false) => {
+                    not_found += 1;
+                    println!("{:?}: not found", chunk_root);
+                }
+                Err(err) => {
+                    failed += 1;
+                    println!("{:?}: failed: {:?}", chunk_root, err);
+                }
+            }
+        }
+
+        println!(
+            "Removed: {}, not found: {}, failed: {}",
+            removed, not_found, failed
+        );
+
+        Ok(())
+    }
+}
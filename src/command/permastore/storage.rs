@@ -0,0 +1,162 @@
+use anyhow::Result;
+use structopt::StructOpt;
+
+use crate::{
+    client::CanyonClient,
+    pallets::permastore::{
+        BlockDataSizeStoreExt, ChunkRootIndexStoreExt, TransactionRootsStoreExt, WeaveSizeStoreExt,
+    },
+    runtime::primitives::BlockNumber,
+};
+
+/// Blocks to retrieve the state at.
+#[derive(Debug, StructOpt)]
+pub struct BlockRange {
+    /// Specify the block number for retrieving the state.
+    #[structopt(long)]
+    block_number: Option<BlockNumber>,
+    /// Retrieve the state of every block from this one, up to `--to`.
+    #[structopt(long, conflicts_with = "block-number")]
+    from: Option<BlockNumber>,
+    /// Last block of the range, defaults to the best block.
+    #[structopt(long, requires = "from")]
+    to: Option<BlockNumber>,
+}
+
+impl BlockRange {
+    /// Returns the block numbers in range, `None` stands for the best block.
+    async fn numbers(&self, client: &CanyonClient) -> Result<Vec<Option<BlockNumber>>> {
+        match self.from {
+            Some(from) => {
+                let to = match self.to {
+                    Some(to) => to,
+                    None => client.best_number().await?,
+                };
+                Ok((from..=to).map(Some).collect())
+            }
+            None => Ok(vec![self.block_number]),
+        }
+    }
+}
+
+/// Returns `size` in bytes and approximately in the largest binary unit below it.
+fn display_byte_size(size: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    let mut n = 0;
+    while n < UNITS.len() && size >= 1024u64.pow(n as u32 + 1) {
+        n += 1;
+    }
+
+    if n == 0 || size >= 1024u64.pow(UNITS.len() as u32 + 1) {
+        size.to_string()
+    } else {
+        format!(
+            "{} ~= {:.*} {}",
+            size,
+            2 * n,
+            size as f64 / 1024u64.pow(n as u32) as f64,
+            UNITS[n - 1]
+        )
+    }
+}
+
+/// Prints `message`, prefixed with the block number when querying a range of blocks.
+fn print_at(number: Option<BlockNumber>, is_range: bool, message: String) {
+    match number {
+        Some(number) if is_range => println!("block #{}: {}", number, message),
+        _ => println!("{}", message),
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Storage {
+    /// Size of entire weave.
+    WeaveSize {
+        #[structopt(flatten)]
+        range: BlockRange,
+    },
+    /// Size of the data stored in a block.
+    BlockDataSize {
+        #[structopt(flatten)]
+        range: BlockRange,
+    },
+    /// Transactions root of a block, i.e. the merkle root of the chunk roots stored in it.
+    TransactionRoots {
+        #[structopt(flatten)]
+        range: BlockRange,
+    },
+    /// Chunk root stored by an extrinsic.
+    ChunkRootIndex {
+        /// Number of the block including the extrinsic.
+        #[structopt(index = 1)]
+        block: BlockNumber,
+        /// Index of the extrinsic in the block.
+        #[structopt(index = 2)]
+        extrinsic_index: u32,
+    },
+}
+
+impl Storage {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        match self {
+            Self::WeaveSize { range } => {
+                let is_range = range.from.is_some();
+                for number in range.numbers(client).await? {
+                    let at = client.block_hash(number).await?;
+                    let weave_size = client.0.weave_size(at).await?;
+                    print_at(
+                        number,
+                        is_range,
+                        format!(
+                            "byte size of entire weave: {}",
+                            display_byte_size(weave_size)
+                        ),
+                    );
+                }
+            }
+            Self::BlockDataSize { range } => {
+                let is_range = range.from.is_some();
+                for number in range.numbers(client).await? {
+                    let at = client.block_hash(number).await?;
+                    let block_data_size = client.0.block_data_size(at).await?;
+                    print_at(
+                        number,
+                        is_range,
+                        format!(
+                            "byte size of block data: {}",
+                            display_byte_size(block_data_size)
+                        ),
+                    );
+                }
+            }
+            Self::TransactionRoots { range } => {
+                let is_range = range.from.is_some();
+                for number in range.numbers(client).await? {
+                    let number = match number {
+                        Some(number) => number,
+                        None => client.best_number().await?,
+                    };
+                    let transactions_root = client.0.transaction_roots(number, None).await?;
+                    print_at(
+                        Some(number),
+                        is_range,
+                        format!("transactions root: {:?}", transactions_root),
+                    );
+                }
+            }
+            Self::ChunkRootIndex {
+                block,
+                extrinsic_index,
+            } => {
+                let chunk_root = client
+                    .0
+                    .chunk_root_index(block, extrinsic_index, None)
+                    .await?;
+                println!("chunk root: {:?}", chunk_root);
+            }
+        }
+
+        Ok(())
+    }
+}
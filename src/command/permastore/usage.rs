@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use codec::Encode;
+use structopt::{clap::arg_enum, StructOpt};
+
+use crate::{
+    client::CanyonClient,
+    runtime::{
+        extrinsic::DecodedExtrinsic,
+        primitives::{AccountId, Balance, BlockNumber},
+    },
+    utils::OutputFormat,
+};
+
+arg_enum! {
+    /// Column used to sort the usage report.
+    #[derive(Clone, Copy, Debug)]
+    pub enum UsageSortKey {
+        Bytes,
+        Count,
+        Fees,
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Usage {
+    /// First block of the range.
+    #[structopt(long)]
+    from: BlockNumber,
+    /// Last block of the range, defaults to the best block.
+    #[structopt(long)]
+    to: Option<BlockNumber>,
+    /// Sort the accounts in descending order of this column.
+    #[structopt(
+        long,
+        possible_values = &UsageSortKey::variants(),
+        case_insensitive = true,
+        default_value = "bytes"
+    )]
+    sort_by: UsageSortKey,
+    /// Output format.
+    #[structopt(
+        long,
+        possible_values = &OutputFormat::variants(),
+        case_insensitive = true,
+        default_value = "table"
+    )]
+    format: OutputFormat,
+}
+
+/// Aggregated storage usage of an account.
+#[derive(Debug, Default)]
+struct AccountUsage {
+    bytes: u64,
+    count: u64,
+    fees: Balance,
+}
+
+impl Usage {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let to = match self.to {
+            Some(to) => to,
+            None => client.best_number().await?,
+        };
+
+        let mut usage = HashMap::<AccountId, AccountUsage>::new();
+
+        for number in self.from..=to {
+            for tx in client.store_transactions(number).await? {
+                let who = match tx.signer {
+                    Some(who) => who,
+                    None => continue,
+                };
+
+                let fee_info = client
+                    .payment_query_info(tx.extrinsic.encode().into(), Some(tx.parent_hash))
+                    .await?;
+                // The tip is paid on top of the inclusion fee.
+                let tip = DecodedExtrinsic::decode(&tx.extrinsic)?
+                    .signature
+                    .map_or(0, |signature| signature.tip.0);
+
+                let entry = usage.entry(who).or_default();
+                entry.bytes += tx.data_size as u64;
+                entry.count += 1;
+                entry.fees += fee_info.partial_fee + tip;
+            }
+        }
+
+        let mut usage = usage.into_iter().collect::<Vec<_>>();
+        match self.sort_by {
+            UsageSortKey::Bytes => usage.sort_by(|(_, a), (_, b)| b.bytes.cmp(&a.bytes)),
+            UsageSortKey::Count => usage.sort_by(|(_, a), (_, b)| b.count.cmp(&a.count)),
+            UsageSortKey::Fees => usage.sort_by(|(_, a), (_, b)| b.fees.cmp(&a.fees)),
+        }
+
+        let token = client.token();
+        let rows = usage
+            .into_iter()
+            .map(|(who, usage)| {
+                vec![
+                    who.to_string(),
+                    usage.bytes.to_string(),
+                    usage.count.to_string(),
+                    token.display(usage.fees),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        self.format
+            .print(&["account", "bytes", "transactions", "fees"], &rows);
+
+        Ok(())
+    }
+}
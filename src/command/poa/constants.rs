@@ -0,0 +1,56 @@
+use anyhow::Result;
+use structopt::StructOpt;
+
+use crate::{
+    client::CanyonClient,
+    runtime::{metadata::decode_value, primitives::BlockNumber},
+    utils::OutputFormat,
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Constants {
+    /// Specify the block number for retrieving the runtime.
+    #[structopt(long)]
+    block_number: Option<BlockNumber>,
+    /// Output format.
+    #[structopt(
+        long,
+        possible_values = &OutputFormat::variants(),
+        case_insensitive = true,
+        default_value = "table"
+    )]
+    format: OutputFormat,
+}
+
+impl Constants {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let token = client.token();
+        let at = client.block_hash(self.block_number).await?;
+        let constants = client.pallet_constants("Poa", at).await?;
+
+        // The poa parameters are mostly kept in the `PoaConfig` storage item, the runtime
+        // may not expose any constant.
+        if constants.is_empty() {
+            eprintln!("The Poa pallet has no constants, see `poa storage config`");
+            return Ok(());
+        }
+
+        let rows = constants
+            .into_iter()
+            .map(|constant| {
+                let value = decode_value(&token, &constant.ty, &constant.value);
+                vec![
+                    constant.name,
+                    constant.ty,
+                    value,
+                    constant.documentation.join(" ").trim().to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        self.format
+            .print(&["name", "type", "value", "documentation"], &rows);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+mod constants;
+mod overview;
+mod proof;
+mod readiness;
+mod recall;
+mod verify;
+mod watch;
+
+use anyhow::{anyhow, Result};
+use codec::Decode;
+use structopt::StructOpt;
+use subxt::Store;
+
+use pallet_poa::DepthInfo;
+
+use crate::{
+    client::CanyonClient,
+    pallets::poa::{HistoryDepthStore, HistoryDepthStoreExt, PoaConfigStoreExt},
+    runtime::{
+        primitives::{AccountId, BlockNumber},
+        CanyonRuntime, CanyonSigner,
+    },
+    utils::{parse_account, OutputFormat},
+};
+
+/// Poa
+#[derive(Debug, StructOpt)]
+pub enum Poa {
+    /// Inspect the poa storage items.
+    Storage(Storage),
+    /// Show the constants of the poa pallet.
+    Constants(constants::Constants),
+    /// Show the history depth of all the current validators.
+    Overview(overview::Overview),
+    /// Decode the PoA proof embedded in a block header.
+    Proof(proof::Proof),
+    /// Compute the recall byte of a block and the transaction storing it.
+    Recall(recall::Recall),
+    /// Estimate how much of the weave can be proven with a local chunk store.
+    Readiness(readiness::Readiness),
+    /// Verify the PoA proof embedded in a block header against the chain state.
+    Verify(verify::Verify),
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Storage {
+    /// Retrieve the history depth of given account.
+    HistoryDepth {
+        /// Account
+        #[structopt(index = 1, long, parse(try_from_str = parse_account))]
+        who: AccountId,
+        /// Specify the block number for retrieving the state.
+        #[structopt(long)]
+        block_number: Option<BlockNumber>,
+        /// Subscribe the storage changes of HistoryDepth.
+        #[structopt(long)]
+        watch: bool,
+        #[structopt(flatten)]
+        alert: watch::AlertParams,
+        /// Sample the history depth from this block, up to `--to`.
+        #[structopt(long, conflicts_with_all = &["block-number", "watch"])]
+        from: Option<BlockNumber>,
+        /// Last block of the samples, defaults to the best block.
+        #[structopt(long, requires = "from")]
+        to: Option<BlockNumber>,
+        /// Number of blocks between two samples.
+        #[structopt(long, default_value = "1")]
+        step: BlockNumber,
+        /// Output format of the samples, `json` prints JSON lines in watch mode.
+        #[structopt(
+            long,
+            possible_values = &OutputFormat::variants(),
+            case_insensitive = true,
+            default_value = "table"
+        )]
+        format: OutputFormat,
+    },
+    /// Retrieve the history depth of all the accounts.
+    HistoryDepths {
+        /// Specify the block number for retrieving the state.
+        #[structopt(long)]
+        block_number: Option<BlockNumber>,
+        /// Output format.
+        #[structopt(
+            long,
+            possible_values = &OutputFormat::variants(),
+            case_insensitive = true,
+            default_value = "table"
+        )]
+        format: OutputFormat,
+    },
+    /// Retrieve the configuration of poa.
+    Config {
+        /// Specify the block number for retrieving the state.
+        #[structopt(long)]
+        block_number: Option<BlockNumber>,
+    },
+}
+
+pub fn storage_ratio(depth_info: &DepthInfo<BlockNumber>) -> f64 {
+    let storage_ratio = depth_info.as_storage_capacity();
+    storage_ratio.deconstruct() as f64 / 1_000_000f64
+}
+
+pub fn display_storage_ratio(depth_info: &DepthInfo<BlockNumber>) -> String {
+    format!("{}", storage_ratio(depth_info))
+}
+
+/// Prints the history depth of `who` sampled every `step` blocks in `[from, to]`.
+async fn history_depth_series(
+    client: &CanyonClient,
+    who: &AccountId,
+    from: BlockNumber,
+    to: BlockNumber,
+    step: BlockNumber,
+    format: OutputFormat,
+) -> Result<()> {
+    if step == 0 {
+        return Err(anyhow!("--step must be greater than 0"));
+    }
+
+    let mut rows = Vec::new();
+    for number in (from..=to).step_by(step as usize) {
+        let at = client.block_hash(Some(number)).await?;
+        let depth_info = client.0.history_depth(who, at).await?;
+        rows.push(vec![
+            number.to_string(),
+            depth_info.blocks.to_string(),
+            depth_info.total_depth.to_string(),
+            display_storage_ratio(&depth_info),
+        ]);
+    }
+
+    format.print(&["block", "blocks", "total depth", "storage ratio"], &rows);
+
+    Ok(())
+}
+
+impl Poa {
+    pub async fn run(self, url: String, signer: CanyonSigner) -> Result<()> {
+        let client = CanyonClient::create(url.clone()).await?;
+
+        match self {
+            Self::Constants(constants) => constants.run(&client).await?,
+            Self::Overview(overview) => overview.run(&client).await?,
+            Self::Proof(proof) => proof.run(&client).await?,
+            Self::Recall(recall) => recall.run(&client).await?,
+            Self::Readiness(readiness) => readiness.run(&client, &signer).await?,
+            Self::Verify(verify) => verify.run(&client).await?,
+            Self::Storage(storage) => match storage {
+                Storage::HistoryDepth {
+                    who,
+                    block_number,
+                    watch,
+                    alert,
+                    from,
+                    to,
+                    step,
+                    format,
+                } => {
+                    if let Some(from) = from {
+                        let to = match to {
+                            Some(to) => to,
+                            None => client.best_number().await?,
+                        };
+                        history_depth_series(&client, &who, from, to, step, format).await?;
+                    } else if watch {
+                        let json = format == OutputFormat::Json;
+                        watch::watch_history_depth(&url, &who, alert, json).await?;
+                    } else {
+                        let at = client.block_hash(block_number).await?;
+                        let key = HistoryDepthStore::<CanyonRuntime> { account_id: &who }
+                            .key(client.metadata());
+                        println!("key: {:?}", key);
+                        let depth_info = client.0.history_depth(&who, at).await?;
+                        println!("{:?}: {:#?}", who, depth_info);
+                        println!(
+                            "Estimated storage ratio: {:?}",
+                            display_storage_ratio(&depth_info)
+                        );
+                    }
+                }
+                Storage::HistoryDepths {
+                    block_number,
+                    format,
+                } => {
+                    let at = client.block_hash(block_number).await?;
+                    let mut iter = client
+                        .0
+                        .iter::<HistoryDepthStore<CanyonRuntime>>(at)
+                        .await?;
+                    let mut rows = Vec::new();
+                    while let Some((key, depth_info)) = iter.next().await? {
+                        // The key ends with the account id hashed by `Blake2_128Concat`.
+                        let who = AccountId::decode(&mut &key.0[key.0.len() - 32..])?;
+                        rows.push(vec![
+                            who.to_string(),
+                            depth_info.blocks.to_string(),
+                            depth_info.total_depth.to_string(),
+                            display_storage_ratio(&depth_info),
+                        ]);
+                    }
+                    format.print(
+                        &["account", "blocks", "total depth", "storage ratio"],
+                        &rows,
+                    );
+                }
+                Storage::Config { block_number } => {
+                    let at = client.block_hash(block_number).await?;
+                    let config = client.0.poa_config(at).await?;
+                    println!("{:#?}", config);
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use futures::future::try_join_all;
+use structopt::{clap::arg_enum, StructOpt};
+use subxt::session::ValidatorsStoreExt;
+
+use crate::{
+    client::CanyonClient, command::poa::display_storage_ratio, pallets::poa::HistoryDepthStoreExt,
+    runtime::primitives::BlockNumber, utils::OutputFormat,
+};
+
+arg_enum! {
+    /// Column used to sort the overview.
+    #[derive(Clone, Copy, Debug)]
+    pub enum OverviewSortKey {
+        Account,
+        Blocks,
+        Depth,
+        Ratio,
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Overview {
+    /// Specify the block number for retrieving the state.
+    #[structopt(long)]
+    block_number: Option<BlockNumber>,
+    /// Sort the validators by this column, numeric columns are in descending order.
+    #[structopt(
+        long,
+        possible_values = &OverviewSortKey::variants(),
+        case_insensitive = true,
+        default_value = "ratio"
+    )]
+    sort_by: OverviewSortKey,
+    /// Output format.
+    #[structopt(
+        long,
+        possible_values = &OutputFormat::variants(),
+        case_insensitive = true,
+        default_value = "table"
+    )]
+    format: OutputFormat,
+}
+
+impl Overview {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let at = client.block_hash(self.block_number).await?;
+        let validators = client.0.validators(at).await?;
+
+        let depth_infos =
+            try_join_all(validators.iter().map(|who| client.0.history_depth(who, at))).await?;
+
+        let mut overview = validators.into_iter().zip(depth_infos).collect::<Vec<_>>();
+        match self.sort_by {
+            OverviewSortKey::Account => {
+                overview.sort_by(|(a, _), (b, _)| a.to_string().cmp(&b.to_string()))
+            }
+            OverviewSortKey::Blocks => overview.sort_by(|(_, a), (_, b)| b.blocks.cmp(&a.blocks)),
+            OverviewSortKey::Depth => {
+                overview.sort_by(|(_, a), (_, b)| b.total_depth.cmp(&a.total_depth))
+            }
+            OverviewSortKey::Ratio => overview
+                .sort_by(|(_, a), (_, b)| b.as_storage_capacity().cmp(&a.as_storage_capacity())),
+        }
+
+        let rows = overview
+            .iter()
+            .map(|(who, depth_info)| {
+                vec![
+                    who.to_string(),
+                    depth_info.blocks.to_string(),
+                    depth_info.total_depth.to_string(),
+                    display_storage_ratio(depth_info),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        self.format.print(
+            &["validator", "blocks", "total depth", "storage ratio"],
+            &rows,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+use codec::Decode;
+use structopt::StructOpt;
+
+use sp_runtime::{traits::Header as HeaderT, DigestItem};
+
+use cp_consensus_poa::{PoaOutcome, POA_ENGINE_ID};
+
+use crate::{
+    client::CanyonClient,
+    command::poa::recall::recall_byte,
+    pallets::permastore::WeaveSizeStoreExt,
+    runtime::primitives::{BlockNumber, Header},
+};
+
+/// Maximum number of chunk bytes displayed.
+const MAX_DISPLAYED_CHUNK_BYTES: usize = 64;
+
+#[derive(Debug, StructOpt)]
+pub struct Proof {
+    /// Number of the block to inspect.
+    #[structopt(long)]
+    block: BlockNumber,
+}
+
+/// Returns the PoA outcome embedded in the digest of `header`.
+pub fn find_poa_outcome(header: &Header) -> Result<Option<PoaOutcome>> {
+    for log in header.digest().logs() {
+        match log {
+            DigestItem::PreRuntime(id, data)
+            | DigestItem::Consensus(id, data)
+            | DigestItem::Seal(id, data)
+                if *id == POA_ENGINE_ID =>
+            {
+                return Ok(Some(PoaOutcome::decode(&mut data.as_slice())?));
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+fn display_bytes(bytes: &[u8]) -> String {
+    if bytes.len() > MAX_DISPLAYED_CHUNK_BYTES {
+        format!(
+            "0x{}.. ({} bytes)",
+            hex::encode(&bytes[..MAX_DISPLAYED_CHUNK_BYTES]),
+            bytes.len()
+        )
+    } else {
+        format!("0x{}", hex::encode(bytes))
+    }
+}
+
+impl Proof {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let (hash, header) = client.header_at(self.block).await?;
+        println!("block #{}: {:?}", self.block, hash);
+
+        let poa = match find_poa_outcome(&header)? {
+            Some(PoaOutcome::Justification(poa)) => poa,
+            Some(outcome) => {
+                println!("PoA outcome: {:?}", outcome);
+                return Ok(());
+            }
+            None => return Err(anyhow!("No PoA digest found in block #{}", self.block)),
+        };
+
+        let parent_hash = *header.parent_hash();
+        let weave_size = client.0.weave_size(Some(parent_hash)).await?;
+
+        println!("            depth: {}", poa.depth);
+        match recall_byte(&parent_hash, weave_size, poa.depth) {
+            Some(recall_byte) => println!(
+                "      recall byte: {} (weave size at parent: {})",
+                recall_byte, weave_size
+            ),
+            None => println!("      recall byte: none, the weave is empty at parent"),
+        }
+        println!("      chunk index: {}", poa.chunk_proof.chunk_index);
+        println!(
+            "            chunk: {}",
+            display_bytes(&poa.chunk_proof.chunk)
+        );
+        println!("transaction proof: {} nodes", poa.tx_path.len());
+        for node in &poa.tx_path {
+            println!("  {}", display_bytes(node));
+        }
+        println!("      chunk proof: {} nodes", poa.chunk_proof.proof.len());
+        for node in &poa.chunk_proof.proof {
+            println!("  {}", display_bytes(node));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+use subxt::Signer;
+
+use sp_runtime::traits::Header as HeaderT;
+
+use crate::{
+    client::{CanyonClient, StoreTransaction},
+    command::{
+        permastore::mirror::data_path,
+        poa::{
+            recall::{find_recall_transaction, recall_byte},
+            storage_ratio,
+        },
+    },
+    pallets::{permastore::WeaveSizeStoreExt, poa::HistoryDepthStoreExt},
+    runtime::{
+        primitives::{AccountId, BlockNumber, Hash},
+        CanyonSigner,
+    },
+    utils::parse_account,
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Readiness {
+    /// Directory of the local chunk store, as written by `permastore mirror`.
+    #[structopt(long, value_name = "PATH", parse(from_os_str))]
+    dir: PathBuf,
+    /// Number of recent blocks whose recall bytes are sampled.
+    #[structopt(long, default_value = "100")]
+    blocks: BlockNumber,
+    /// Number of the largest missing transactions to list.
+    #[structopt(long, default_value = "10")]
+    top: usize,
+    /// Account whose on-chain storage ratio is compared, defaults to the signer.
+    #[structopt(long, parse(try_from_str = parse_account))]
+    who: Option<AccountId>,
+}
+
+impl Readiness {
+    pub async fn run(self, client: &CanyonClient, signer: &CanyonSigner) -> Result<()> {
+        let best = client.best_number().await?;
+        let from = best.saturating_sub(self.blocks).max(1);
+
+        let (mut sampled, mut available) = (0u32, 0u32);
+        let mut missing = HashMap::<Hash, (StoreTransaction, u32)>::new();
+
+        // The recall byte of block `number` is derived from its parent.
+        for number in from..=best {
+            let (_, header) = client.header_at(number).await?;
+            let parent_hash = *header.parent_hash();
+            let weave_size = client.0.weave_size(Some(parent_hash)).await?;
+            let recall_byte = match recall_byte(&parent_hash, weave_size, 1) {
+                Some(recall_byte) => recall_byte,
+                None => continue,
+            };
+
+            let (tx, _offset) = find_recall_transaction(client, recall_byte, number - 1).await?;
+            sampled += 1;
+            if data_path(&self.dir, &tx.chunk_root).exists() {
+                available += 1;
+            } else {
+                missing.entry(tx.chunk_root).or_insert((tx, 0)).1 += 1;
+            }
+        }
+
+        if sampled == 0 {
+            return Err(anyhow!("The weave is empty, nothing to sample"));
+        }
+
+        let who = match self.who {
+            Some(who) => who,
+            None => signer.account_id().clone(),
+        };
+        let depth_info = client.0.history_depth(&who, None).await?;
+
+        println!(
+            "   sampled recall bytes: {} in blocks #{} - #{}",
+            sampled, from, best
+        );
+        println!(
+            "      locally available: {} ({:.4})",
+            available,
+            available as f64 / sampled as f64
+        );
+        println!(
+            " on-chain storage ratio: {} of {}",
+            storage_ratio(&depth_info),
+            who
+        );
+
+        let mut missing = missing.into_values().collect::<Vec<_>>();
+        missing.sort_by(|(a, _), (b, _)| b.data_size.cmp(&a.data_size));
+
+        if !missing.is_empty() {
+            println!();
+            println!("Largest missing transactions:");
+            for (tx, hits) in missing.into_iter().take(self.top) {
+                println!(
+                    "  #{}-{}: chunk root {:?}, {} bytes, recalled {} times",
+                    tx.block_number, tx.extrinsic_index, tx.chunk_root, tx.data_size, hits
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
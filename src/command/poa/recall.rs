@@ -0,0 +1,140 @@
+use anyhow::{anyhow, Result};
+use structopt::StructOpt;
+
+use sp_core::hashing::blake2_256;
+
+use crate::{
+    client::{CanyonClient, StoreTransaction},
+    pallets::permastore::WeaveSizeStoreExt,
+    runtime::primitives::{BlockNumber, Hash},
+    utils::parse_hash,
+};
+
+/// Returns the recall byte of the weave at `depth` for the block built on `parent_hash`.
+///
+/// The seed is the parent hash hashed `depth` times with blake2-256, the first 8 bytes
+/// of the seed in little endian modulo the weave size is the recall byte.
+pub fn recall_byte(parent_hash: &Hash, weave_size: u64, depth: u32) -> Option<u64> {
+    if weave_size == 0 {
+        return None;
+    }
+
+    let mut seed = blake2_256(parent_hash.as_bytes());
+    for _ in 1..depth {
+        seed = blake2_256(&seed);
+    }
+
+    let mut random_u64 = [0u8; 8];
+    random_u64.copy_from_slice(&seed[..8]);
+
+    Some(u64::from_le_bytes(random_u64) % weave_size)
+}
+
+/// Returns the size of entire weave at block `number`.
+async fn weave_size_at(client: &CanyonClient, number: BlockNumber) -> Result<u64> {
+    let at = client.block_hash(Some(number)).await?;
+    Ok(client.0.weave_size(at).await?)
+}
+
+/// Returns the `store` transaction containing the weave byte `recall_byte` and the offset
+/// of the byte in the transaction data, searching the blocks up to `best`.
+pub async fn find_recall_transaction(
+    client: &CanyonClient,
+    recall_byte: u64,
+    best: BlockNumber,
+) -> Result<(StoreTransaction, u64)> {
+    // Binary search the first block whose weave size is larger than the recall byte.
+    let (mut low, mut high) = (0, best);
+    if weave_size_at(client, high).await? <= recall_byte {
+        return Err(anyhow!(
+            "Recall byte {} is out of the weave at block #{}",
+            recall_byte,
+            best
+        ));
+    }
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if weave_size_at(client, mid).await? > recall_byte {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    let number = low;
+
+    let mut offset = if number > 0 {
+        weave_size_at(client, number - 1).await?
+    } else {
+        0
+    };
+
+    for tx in client.store_transactions(number).await? {
+        let data_size = tx.data_size as u64;
+        if recall_byte < offset + data_size {
+            return Ok((tx, recall_byte - offset));
+        }
+        offset += data_size;
+    }
+
+    Err(anyhow!(
+        "No transaction in block #{} contains the recall byte {}",
+        number,
+        recall_byte
+    ))
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Recall {
+    /// Hash of the parent block.
+    #[structopt(long, parse(try_from_str = parse_hash), required_unless = "block")]
+    parent_hash: Option<Hash>,
+    /// Number of the block to author, its parent is block `N - 1`.
+    #[structopt(long, value_name = "N", conflicts_with = "parent-hash")]
+    block: Option<BlockNumber>,
+    /// Depth of the recall, the block author moves to the next depth when it fails to
+    /// prove the recall byte of the current one.
+    #[structopt(long, default_value = "1")]
+    depth: u32,
+}
+
+impl Recall {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let parent_hash = match (self.parent_hash, self.block) {
+            (Some(parent_hash), _) => parent_hash,
+            (None, Some(0)) => return Err(anyhow!("Genesis block has no parent")),
+            (None, Some(number)) => client
+                .block_hash(Some(number - 1))
+                .await?
+                .ok_or_else(|| anyhow!("Parent block #{} not found", number - 1))?,
+            (None, None) => return Err(anyhow!("--parent-hash or --block is required")),
+        };
+        let parent_number = client
+            .block_number(parent_hash)
+            .await?
+            .ok_or_else(|| anyhow!("Parent block {:?} not found", parent_hash))?;
+
+        let weave_size = client.0.weave_size(Some(parent_hash)).await?;
+        let recall_byte = recall_byte(&parent_hash, weave_size, self.depth)
+            .ok_or_else(|| anyhow!("The weave is empty at block #{}", parent_number))?;
+
+        let (tx, offset) = find_recall_transaction(client, recall_byte, parent_number).await?;
+        let chunk_index = offset / client.chunk_size() as u64;
+
+        println!(" parent block: #{} {:?}", parent_number, parent_hash);
+        println!("   weave size: {}", weave_size);
+        println!("  recall byte: {} at depth {}", recall_byte, self.depth);
+        println!(
+            "  transaction: #{}-{} in block {:?}",
+            tx.block_number, tx.extrinsic_index, tx.block_hash
+        );
+        if let Some(signer) = tx.signer {
+            println!("       signer: {}", signer);
+        }
+        println!("   chunk root: {:?}", tx.chunk_root);
+        println!("    data size: {}", tx.data_size);
+        println!("  byte offset: {}", offset);
+        println!("  chunk index: {}", chunk_index);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::{anyhow, Result};
+use codec::{Compact, Encode};
+use structopt::StructOpt;
+
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT, Header as HeaderT};
+use sp_trie::{verify_trie_proof, Layout};
+
+use cp_consensus_poa::PoaOutcome;
+
+use crate::{
+    client::CanyonClient,
+    command::poa::{
+        proof::find_poa_outcome,
+        recall::{find_recall_transaction, recall_byte},
+    },
+    pallets::permastore::WeaveSizeStoreExt,
+    runtime::primitives::{BlockNumber, Hash},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct Verify {
+    /// Number of the block to verify.
+    #[structopt(long)]
+    block: BlockNumber,
+}
+
+/// Reports a passed verification step.
+fn pass(step: &str, detail: String) {
+    println!("[ok]   {}: {}", step, detail);
+}
+
+/// Reports a failed verification step and returns the error to abort the verification.
+fn fail(step: &str, detail: String) -> anyhow::Error {
+    println!("[fail] {}: {}", step, detail);
+    anyhow!("PoA verification of the block failed at step: {}", step)
+}
+
+/// Verifies `proof` proves `value` at the position `index` of the ordered trie `root`.
+fn verify_ordered_trie_proof(root: &Hash, proof: &[Vec<u8>], index: u32, value: Vec<u8>) -> bool {
+    let key = Compact(index).encode();
+    verify_trie_proof::<Layout<BlakeTwo256>, _, _, _>(root, proof, &[(key, Some(value))]).is_ok()
+}
+
+impl Verify {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        let (hash, header) = client.header_at(self.block).await?;
+        println!("Verifying PoA of block #{}: {:?}", self.block, hash);
+
+        let step = "decode PoA digest";
+        let poa = match find_poa_outcome(&header) {
+            Ok(Some(PoaOutcome::Justification(poa))) => {
+                pass(step, format!("depth {}", poa.depth));
+                poa
+            }
+            Ok(Some(outcome)) => {
+                pass(step, format!("{:?}, nothing to verify", outcome));
+                return Ok(());
+            }
+            Ok(None) => return Err(fail(step, "no PoA digest in the header".into())),
+            Err(err) => return Err(fail(step, format!("{:?}", err))),
+        };
+
+        let step = "compute recall byte";
+        let parent_hash = *header.parent_hash();
+        let weave_size = client.0.weave_size(Some(parent_hash)).await?;
+        let recall_byte = recall_byte(&parent_hash, weave_size, poa.depth)
+            .ok_or_else(|| fail(step, "the weave is empty at the parent block".into()))?;
+        pass(
+            step,
+            format!("{} of weave size {}", recall_byte, weave_size),
+        );
+
+        let step = "locate recall transaction";
+        let (tx, offset) = find_recall_transaction(client, recall_byte, self.block - 1)
+            .await
+            .map_err(|err| fail(step, format!("{:?}", err)))?;
+        pass(
+            step,
+            format!(
+                "extrinsic #{}-{}, chunk root {:?}, byte offset {}",
+                tx.block_number, tx.extrinsic_index, tx.chunk_root, offset
+            ),
+        );
+
+        let step = "check chunk index";
+        let expected_chunk_index = (offset / client.chunk_size() as u64) as u32;
+        if poa.chunk_proof.chunk_index != expected_chunk_index {
+            return Err(fail(
+                step,
+                format!(
+                    "expected {}, got {}",
+                    expected_chunk_index, poa.chunk_proof.chunk_index
+                ),
+            ));
+        }
+        pass(step, format!("{}", expected_chunk_index));
+
+        let step = "verify transaction proof";
+        let (_, recall_header) = client.header_at(tx.block_number).await?;
+        let extrinsics_root = *recall_header.extrinsics_root();
+        if !verify_ordered_trie_proof(
+            &extrinsics_root,
+            &poa.tx_path,
+            tx.extrinsic_index,
+            tx.extrinsic.encode(),
+        ) {
+            return Err(fail(
+                step,
+                format!(
+                    "extrinsic not proven against extrinsics root {:?}",
+                    extrinsics_root
+                ),
+            ));
+        }
+        pass(step, format!("extrinsics root {:?}", extrinsics_root));
+
+        let step = "verify chunk proof";
+        let chunk_hash = BlakeTwo256::hash(&poa.chunk_proof.chunk);
+        if !verify_ordered_trie_proof(
+            &tx.chunk_root,
+            &poa.chunk_proof.proof,
+            poa.chunk_proof.chunk_index,
+            chunk_hash.encode(),
+        ) {
+            return Err(fail(
+                step,
+                format!("chunk not proven against chunk root {:?}", tx.chunk_root),
+            ));
+        }
+        pass(step, format!("chunk hash {:?}", chunk_hash));
+
+        println!("PoA of block #{} is valid", self.block);
+
+        Ok(())
+    }
+}
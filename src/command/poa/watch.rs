@@ -0,0 +1,243 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use codec::Decode;
+use structopt::StructOpt;
+
+use sp_core::storage::StorageChangeSet;
+
+use pallet_poa::DepthInfo;
+
+use crate::{
+    client::CanyonClient,
+    command::poa::{display_storage_ratio, storage_ratio},
+    hook::HookParams,
+    runtime::primitives::{AccountId, BlockNumber, Hash},
+    utils::{subscribe_with_backoff, Resubscribe},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct AlertParams {
+    /// Alert when the estimated storage ratio drops below this value in watch mode.
+    #[structopt(long, value_name = "RATIO", requires = "watch")]
+    alert_below: Option<f64>,
+
+    /// The storage ratio has to rise this much above `--alert-below` to clear the alert.
+    #[structopt(long, value_name = "RATIO", default_value = "0.05")]
+    hysteresis: f64,
+
+    #[structopt(flatten)]
+    hook: HookParams,
+}
+
+/// Threshold alert with hysteresis on the storage ratio.
+#[derive(Debug)]
+struct RatioAlert {
+    threshold: f64,
+    hysteresis: f64,
+    alerting: bool,
+}
+
+impl RatioAlert {
+    fn new(threshold: f64, hysteresis: f64) -> Self {
+        Self {
+            threshold,
+            hysteresis,
+            alerting: false,
+        }
+    }
+
+    /// Returns `Some(true)` when the alert is raised, `Some(false)` when it's cleared.
+    fn update(&mut self, ratio: f64) -> Option<bool> {
+        if !self.alerting && ratio < self.threshold {
+            self.alerting = true;
+            Some(true)
+        } else if self.alerting && ratio >= self.threshold + self.hysteresis {
+            self.alerting = false;
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Watcher of the history depth of an account.
+struct HistoryDepthWatcher<'a> {
+    who: &'a AccountId,
+    alert: AlertParams,
+    ratio_alert: Option<RatioAlert>,
+    /// Print the changes as JSON lines.
+    json: bool,
+    /// The last block number and depth info we have seen.
+    last_seen: Option<(BlockNumber, DepthInfo<BlockNumber>)>,
+}
+
+impl<'a> HistoryDepthWatcher<'a> {
+    /// Handles the depth info of `who` at block `number`, `backfilled` indicates
+    /// it's retrieved by a historical query instead of the subscription.
+    async fn on_depth_info(
+        &mut self,
+        number: BlockNumber,
+        block: Hash,
+        depth_info: DepthInfo<BlockNumber>,
+        backfilled: bool,
+    ) {
+        if matches!(self.last_seen, Some((_, ref last)) if *last == depth_info) {
+            self.last_seen = Some((number, depth_info));
+            return;
+        }
+
+        let ratio = storage_ratio(&depth_info);
+        if self.json {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "block": number,
+                    "blockHash": format!("{:?}", block),
+                    "blocks": depth_info.blocks,
+                    "totalDepth": depth_info.total_depth,
+                    "storageRatio": ratio,
+                    "backfilled": backfilled,
+                })
+            );
+        } else {
+            println!(
+                "block #{}: {}, new_depth_info: {:?}, estimated storage ratio: {}{}",
+                number,
+                block,
+                depth_info,
+                display_storage_ratio(&depth_info),
+                if backfilled { " (backfilled)" } else { "" }
+            );
+        }
+
+        if let Some(raised) = self.ratio_alert.as_mut().and_then(|a| a.update(ratio)) {
+            let status = if raised { "alert" } else { "resolved" };
+            if !self.json {
+                println!(
+                    "[{}] estimated storage ratio of {} is {} at block #{}",
+                    status, self.who, ratio, number
+                );
+            }
+            self.alert
+                .hook
+                .notify(&serde_json::json!({
+                    "status": status,
+                    "account": self.who.to_string(),
+                    "block": number,
+                    "blockHash": format!("{:?}", block),
+                    "storageRatio": ratio,
+                    "threshold": self.alert.alert_below,
+                }))
+                .await;
+        }
+
+        self.last_seen = Some((number, depth_info));
+    }
+
+    /// Returns the hash of block `number` and the depth info of `who` at that block.
+    async fn depth_info_at(
+        &self,
+        client: &CanyonClient,
+        number: BlockNumber,
+    ) -> Result<(Hash, DepthInfo<BlockNumber>)> {
+        let block = client
+            .block_hash(Some(number))
+            .await?
+            .ok_or_else(|| anyhow!("Block #{} not found", number))?;
+        let depth_info = client.0.history_depth(self.who, Some(block)).await?;
+        Ok((block, depth_info))
+    }
+
+    /// Queries the depth info of the blocks between the last seen block and `number`.
+    ///
+    /// The blocks that can not be queried, e.g. the pruned ones, are reported as a gap
+    /// instead of interrupting the subscription.
+    async fn backfill(&mut self, client: &CanyonClient, number: BlockNumber) {
+        let last_number = match self.last_seen {
+            Some((last_number, _)) => last_number,
+            None => return,
+        };
+        for missed in last_number + 1..number {
+            match self.depth_info_at(client, missed).await {
+                Ok((block, depth_info)) => {
+                    self.on_depth_info(missed, block, depth_info, true).await
+                }
+                Err(err) => {
+                    eprintln!(
+                        "Unable to backfill blocks #{}..#{}: {:?}",
+                        missed,
+                        number - 1,
+                        err
+                    );
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> Resubscribe for HistoryDepthWatcher<'a> {
+    /// Subscribes to the history depth until the subscription fails or ends.
+    async fn run_subscription(&mut self, url: &str, connected: &mut bool) -> Result<()> {
+        let client = CanyonClient::create(url).await?;
+        let mut subscription = client.watch_poa_history_depth(self.who).await?;
+        *connected = true;
+
+        let mut resumed = self.last_seen.is_some();
+
+        loop {
+            let StorageChangeSet { block, changes } = match subscription.next().await {
+                Ok(Some(change_set)) => change_set,
+                Ok(None) => return Err(anyhow!("Subscription closed")),
+                Err(err) => return Err(err.into()),
+            };
+
+            // We only subscribed one key.
+            let data = match changes.into_iter().next() {
+                Some((_storage_key, Some(data))) => data,
+                _ => continue,
+            };
+            let depth_info: DepthInfo<BlockNumber> = Decode::decode(&mut data.0.as_slice())?;
+            let number = client
+                .block_number(block)
+                .await?
+                .ok_or_else(|| anyhow!("Block {:?} not found", block))?;
+
+            // The first notification after a reconnection carries the latest value,
+            // the changes happened in the meanwhile have to be queried.
+            if resumed {
+                self.backfill(&client, number).await;
+                resumed = false;
+            }
+
+            self.on_depth_info(number, block, depth_info, false).await;
+        }
+    }
+}
+
+/// Subscribes to the history depth of `who`, prints the changes and alerts on low storage ratio.
+///
+/// The subscription is recreated with an exponential backoff if the connection is lost.
+pub async fn watch_history_depth(
+    url: &str,
+    who: &AccountId,
+    alert: AlertParams,
+    json: bool,
+) -> Result<()> {
+    if alert.hysteresis < 0f64 {
+        return Err(anyhow!("--hysteresis can not be negative"));
+    }
+
+    let mut watcher = HistoryDepthWatcher {
+        who,
+        ratio_alert: alert
+            .alert_below
+            .map(|threshold| RatioAlert::new(threshold, alert.hysteresis)),
+        alert,
+        json,
+        last_seen: None,
+    };
+
+    subscribe_with_backoff(url, "history depth", &mut watcher).await
+}
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use codec::Encode;
+use structopt::StructOpt;
+use subxt::{system::Phase, Raw};
+
+use crate::{
+    client::CanyonClient,
+    runtime::{
+        extrinsic::{is_extrinsic_success, DecodedExtrinsic},
+        primitives::{AccountId, BlockNumber},
+    },
+    utils::{parse_account, OutputFormat},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct History {
+    /// Account to list the extrinsics and events of.
+    #[structopt(index = 1, parse(try_from_str = parse_account))]
+    who: AccountId,
+    /// First block of the range.
+    #[structopt(long)]
+    from: BlockNumber,
+    /// Last block of the range, defaults to the best block.
+    #[structopt(long)]
+    to: Option<BlockNumber>,
+    /// Number of records per page.
+    #[structopt(long, default_value = "50")]
+    page_size: usize,
+    /// Page to show, starting from 1.
+    #[structopt(long, default_value = "1")]
+    page: usize,
+    /// Output format.
+    #[structopt(
+        long,
+        possible_values = &OutputFormat::variants(),
+        case_insensitive = true,
+        default_value = "table"
+    )]
+    format: OutputFormat,
+}
+
+impl History {
+    pub async fn run(self, client: &CanyonClient) -> Result<()> {
+        if self.page == 0 || self.page_size == 0 {
+            return Err(anyhow!("--page and --page-size must be positive"));
+        }
+
+        let token = client.token();
+        let to = match self.to {
+            Some(to) => to,
+            None => client.best_number().await?,
+        };
+        // The calls and events are decoded with the runtime at the end of the range.
+        let registry = client
+            .argument_registry(client.block_hash(Some(to)).await?)
+            .await?;
+
+        let who_bytes: &[u8] = self.who.as_ref();
+        let skip = (self.page - 1) * self.page_size;
+        let mut seen = 0;
+        let mut has_more = false;
+        let mut rows = Vec::new();
+
+        'blocks: for number in self.from..=to {
+            let (hash, block) = client.block_at(number).await?;
+            let events = client.events(hash).await?;
+            let mut records = Vec::new();
+
+            // Extrinsics signed by `who`, their events are implied by the extrinsic.
+            let mut own_extrinsics = HashSet::new();
+            for (index, xt) in block.extrinsics.iter().enumerate() {
+                let decoded = DecodedExtrinsic::decode(xt)?;
+                if decoded.signer() != Some(&self.who) {
+                    continue;
+                }
+                let index = index as u32;
+                own_extrinsics.insert(index);
+
+                let fee = client
+                    .payment_query_info(xt.encode().into(), Some(block.header.parent_hash))
+                    .await?
+                    .partial_fee;
+                let tip = decoded
+                    .signature
+                    .as_ref()
+                    .map_or(0, |signature| signature.tip.0);
+                let (pallet, name, arguments) = match registry.decode_call(&token, &decoded.call) {
+                    Some((definition, arguments)) => (
+                        definition.pallet.clone(),
+                        definition.name.clone(),
+                        arguments,
+                    ),
+                    None => (
+                        String::new(),
+                        String::new(),
+                        format!("0x{}", hex::encode(&decoded.call)),
+                    ),
+                };
+                let result = if is_extrinsic_success(&events, index) {
+                    "success"
+                } else {
+                    "failed"
+                };
+
+                records.push(vec![
+                    number.to_string(),
+                    index.to_string(),
+                    "extrinsic".into(),
+                    pallet,
+                    name,
+                    result.into(),
+                    token.display(fee + tip),
+                    arguments,
+                ]);
+            }
+
+            // Events of the other extrinsics or of the block itself mentioning `who`.
+            for (phase, raw) in &events {
+                let event = match raw {
+                    Raw::Event(event) => event,
+                    Raw::Error(_) => continue,
+                };
+                let index = match phase {
+                    Phase::ApplyExtrinsic(index) => Some(*index),
+                    _ => None,
+                };
+                if index.map_or(false, |index| own_extrinsics.contains(&index)) {
+                    continue;
+                }
+                if !event.data.windows(32).any(|bytes| bytes == who_bytes) {
+                    continue;
+                }
+
+                records.push(vec![
+                    number.to_string(),
+                    index.map(|index| index.to_string()).unwrap_or_default(),
+                    "event".into(),
+                    event.module.clone(),
+                    event.variant.clone(),
+                    String::new(),
+                    String::new(),
+                    registry.decode_event(&token, &event.module, &event.variant, &event.data),
+                ]);
+            }
+
+            for record in records {
+                seen += 1;
+                if seen <= skip {
+                    continue;
+                }
+                if rows.len() == self.page_size {
+                    has_more = true;
+                    break 'blocks;
+                }
+                rows.push(record);
+            }
+        }
+
+        self.format.print(
+            &[
+                "block_number",
+                "index",
+                "kind",
+                "pallet",
+                "name",
+                "result",
+                "fee",
+                "arguments",
+            ],
+            &rows,
+        );
+
+        if has_more {
+            eprintln!(
+                "More records available, rerun with `--page {}`",
+                self.page + 1
+            );
+        }
+
+        Ok(())
+    }
+}
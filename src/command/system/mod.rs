@@ -2,7 +2,12 @@ use std::path::PathBuf;
 
 use anyhow::Result;
 use structopt::StructOpt;
-use subxt::system::{AccountStoreExt, SetCodeWithoutChecksCallExt};
+use subxt::{
+    balances::AccountData,
+    system::{AccountInfo, AccountStoreExt, SetCodeWithoutChecksCallExt},
+};
+
+mod history;
 
 use crate::{
     client::CanyonClient,
@@ -25,6 +30,8 @@ pub enum System {
         #[structopt(long)]
         block_number: Option<BlockNumber>,
     },
+    /// List the signed extrinsics of an account and the events mentioning it.
+    History(history::History),
     /// Set code without checking.
     SetCodeWithoutChecks {
         /// Code path
@@ -40,9 +47,31 @@ impl System {
         match self {
             Self::AccountInfo { who, block_number } => {
                 let at = client.block_hash(block_number).await?;
-                let account_info = client.0.account(&who, at).await?;
-                println!("AccountInfo of {:?}: {:#?}", who, account_info);
+                let AccountInfo {
+                    nonce,
+                    consumers,
+                    providers,
+                    sufficients,
+                    data:
+                        AccountData {
+                            free,
+                            reserved,
+                            misc_frozen,
+                            fee_frozen,
+                        },
+                } = client.0.account(&who, at).await?;
+                let token = client.token();
+                println!("AccountInfo of {}:", who);
+                println!("        nonce: {}", nonce);
+                println!("    consumers: {}", consumers);
+                println!("    providers: {}", providers);
+                println!("  sufficients: {}", sufficients);
+                println!("         free: {}", token.display(free));
+                println!("     reserved: {}", token.display(reserved));
+                println!("  misc frozen: {}", token.display(misc_frozen));
+                println!("   fee frozen: {}", token.display(fee_frozen));
             }
+            Self::History(history) => history.run(&client).await?,
             Self::SetCodeWithoutChecks { code } => {
                 let result = client
                     .0
@@ -0,0 +1,107 @@
+use std::{
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use async_std::{
+    future::timeout,
+    io::{ReadExt, WriteExt},
+    net::TcpStream,
+    task,
+};
+use structopt::StructOpt;
+
+/// Environment variable holding the JSON payload passed to the hook command.
+const PAYLOAD_ENV: &str = "CANYON_PAYLOAD";
+
+/// Maximum time given to a hook, so that a stuck hook never stalls the watcher.
+const HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Hooks run on a notification.
+#[derive(Debug, Clone, StructOpt)]
+pub struct HookParams {
+    /// Shell command to run on a notification, the JSON payload is passed in `CANYON_PAYLOAD`.
+    #[structopt(long, value_name = "COMMAND")]
+    pub hook_command: Option<String>,
+
+    /// Local HTTP endpoint to POST the JSON payload to, e.g. `http://127.0.0.1:8080/alert`.
+    #[structopt(long, value_name = "URL")]
+    pub hook_url: Option<String>,
+}
+
+impl HookParams {
+    /// Runs all the configured hooks with `payload`.
+    ///
+    /// A failed hook is reported but does not prevent the others from running.
+    pub async fn notify(&self, payload: &serde_json::Value) {
+        if let Some(ref command) = self.hook_command {
+            if let Err(err) = run_command(command, payload).await {
+                eprintln!("Failed to run hook command: {:?}", err);
+            }
+        }
+        if let Some(ref url) = self.hook_url {
+            if let Err(err) = http_post(url, payload).await {
+                eprintln!("Failed to post to hook url: {:?}", err);
+            }
+        }
+    }
+}
+
+/// Runs `command` on a blocking thread, a command exceeding the timeout is left running.
+async fn run_command(command: &str, payload: &serde_json::Value) -> Result<()> {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .env(PAYLOAD_ENV, payload.to_string())
+        .stdin(Stdio::null());
+    let status = timeout(HOOK_TIMEOUT, task::spawn_blocking(move || cmd.status()))
+        .await
+        .map_err(|_| anyhow!("`{}` timed out", command))??;
+    if !status.success() {
+        return Err(anyhow!("`{}` exited with {}", command, status));
+    }
+    Ok(())
+}
+
+/// Posts `payload` to a plain `http://` url.
+async fn http_post(url: &str, payload: &serde_json::Value) -> Result<()> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| anyhow!("Only http:// urls are supported, got {}", url))?;
+    let (host, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], &rest[pos..]),
+        None => (rest, "/"),
+    };
+    let addr = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let body = payload.to_string();
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+        Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        body.len(),
+        body
+    );
+
+    let response = timeout(HOOK_TIMEOUT, async {
+        let mut stream = TcpStream::connect(addr).await?;
+        stream.write_all(request.as_bytes()).await?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok::<_, std::io::Error>(response)
+    })
+    .await
+    .map_err(|_| anyhow!("{} timed out", url))??;
+    let status_line = response.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(()),
+        _ => Err(anyhow!("Unexpected response from {}: {}", url, status_line)),
+    }
+}
@@ -1,8 +1,10 @@
 pub mod app;
 pub mod client;
 pub mod command;
+pub mod hook;
 pub mod pallets;
 pub mod runtime;
+pub mod token;
 pub mod utils;
 
 use anyhow::Result;
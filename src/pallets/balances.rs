@@ -0,0 +1,61 @@
+//! Balances calls and events not covered by `subxt::balances`.
+
+use codec::{Decode, Encode};
+use subxt::{balances::Balances, system::System, Call, Event};
+
+const MODULE: &str = "Balances";
+
+/// Transfer some balances to another account, failing if the sender would be reaped.
+#[derive(Clone, Debug, PartialEq, Call, Encode)]
+pub struct TransferKeepAliveCall<'a, T: Balances> {
+    /// Destination of the transfer.
+    pub to: &'a <T as System>::Address,
+    /// Amount to transfer.
+    #[codec(compact)]
+    pub amount: T::Balance,
+}
+
+/// Transfer the entire transferable balance to another account.
+#[derive(Clone, Debug, PartialEq, Call, Encode)]
+pub struct TransferAllCall<'a, T: Balances> {
+    /// Destination of the transfer.
+    pub to: &'a <T as System>::Address,
+    /// Leave the existential deposit in the sender account to keep it alive.
+    pub keep_alive: bool,
+}
+
+/// Transfer some balances between two accounts, requires root.
+#[derive(Clone, Debug, PartialEq, Call, Encode)]
+pub struct ForceTransferCall<'a, T: Balances> {
+    /// Source of the transfer.
+    pub source: &'a <T as System>::Address,
+    /// Destination of the transfer.
+    pub dest: &'a <T as System>::Address,
+    /// Amount to transfer.
+    #[codec(compact)]
+    pub amount: T::Balance,
+}
+
+/// Set the free and reserved balances of an account, requires root.
+#[derive(Clone, Debug, PartialEq, Call, Encode)]
+pub struct SetBalanceCall<'a, T: Balances> {
+    /// Account to set the balances of.
+    pub who: &'a <T as System>::Address,
+    /// New free balance.
+    #[codec(compact)]
+    pub new_free: T::Balance,
+    /// New reserved balance.
+    #[codec(compact)]
+    pub new_reserved: T::Balance,
+}
+
+/// The balances of an account have been set by root.
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BalanceSetEvent<T: Balances> {
+    /// Account whose balances have been set.
+    pub who: T::AccountId,
+    /// New free balance.
+    pub free: T::Balance,
+    /// New reserved balance.
+    pub reserved: T::Balance,
+}
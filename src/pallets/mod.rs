@@ -1,2 +1,4 @@
+pub mod balances;
 pub mod permastore;
 pub mod poa;
+pub mod utility;
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
-use codec::Encode;
-use subxt::{balances::Balances, module, system::System, Call, Store};
+use codec::{Decode, Encode};
+use subxt::{balances::Balances, module, system::System, Call, Event, Store};
 
 #[module]
 pub trait Permastore: Balances + System {}
@@ -33,3 +33,37 @@ pub struct WeaveSizeStore<T: Permastore> {
     #[store(returns = u64)]
     pub _runtime: PhantomData<T>,
 }
+
+/// The byte size of the data stored in the current block.
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct BlockDataSizeStore<T: Permastore> {
+    #[store(returns = u64)]
+    pub _runtime: PhantomData<T>,
+}
+
+/// The transactions root of a block, i.e. the merkle root of the chunk roots stored in it.
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct TransactionRootsStore<T: Permastore> {
+    #[store(returns = Option<T::Hash>)]
+    /// Number of the block.
+    pub block_number: T::BlockNumber,
+}
+
+/// The chunk root stored by an extrinsic.
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct ChunkRootIndexStore<T: Permastore> {
+    #[store(returns = Option<T::Hash>)]
+    /// Number of the block including the extrinsic.
+    pub block_number: T::BlockNumber,
+    /// Index of the extrinsic in the block.
+    pub extrinsic_index: u32,
+}
+
+/// Some data has been stored.
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct StoredEvent<T: Permastore> {
+    /// Account storing the data.
+    pub who: T::AccountId,
+    /// Merkle root of the stored data in chunks.
+    pub chunk_root: T::Hash,
+}
@@ -1,6 +1,9 @@
-use codec::Encode;
-use subxt::{module, system::System, Store};
+use std::marker::PhantomData;
 
+use codec::{Decode, Encode};
+use subxt::{module, system::System, Event, Store};
+
+use cp_consensus_poa::PoaConfiguration;
 use pallet_poa::DepthInfo;
 
 #[module]
@@ -13,3 +16,19 @@ pub struct HistoryDepthStore<'a, T: System> {
     /// Account to retrieve the `AccountInfo<T>` for.
     pub account_id: &'a T::AccountId,
 }
+
+/// The configuration of the poa module.
+#[derive(Clone, Debug, Eq, PartialEq, Store, Encode)]
+pub struct PoaConfigStore<T: Poa> {
+    #[store(returns = PoaConfiguration)]
+    pub _runtime: PhantomData<T>,
+}
+
+/// The poa configuration has been updated.
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct ConfigUpdatedEvent<T: Poa> {
+    /// The new configuration.
+    pub config: PoaConfiguration,
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+}
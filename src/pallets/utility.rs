@@ -0,0 +1,36 @@
+use std::marker::PhantomData;
+
+use codec::{Decode, Encode};
+use subxt::{module, system::System, Call, Encoded, Event};
+
+use sp_runtime::DispatchError;
+
+#[module]
+pub trait Utility: System {}
+
+/// Send a batch of calls, all of them are reverted if any of them fails.
+#[derive(Clone, Debug, PartialEq, Call, Encode)]
+pub struct BatchAllCall<'a, T: Utility> {
+    /// Encoded calls to dispatch.
+    pub calls: &'a [Encoded],
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+}
+
+/// A batch was interrupted by a failing call.
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BatchInterruptedEvent<T: Utility> {
+    /// Index of the failing call.
+    pub index: u32,
+    /// Error of the failing call.
+    pub error: DispatchError,
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+}
+
+/// A batch completed successfully.
+#[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
+pub struct BatchCompletedEvent<T: Utility> {
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+}
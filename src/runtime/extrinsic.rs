@@ -0,0 +1,102 @@
+use anyhow::{anyhow, Result};
+use codec::{Compact, Decode, Encode};
+
+use subxt::{system::Phase, Raw};
+
+use sp_runtime::{generic::Era, MultiAddress, OpaqueExtrinsic};
+
+use super::primitives::{AccountId, Address, Balance, Index, Signature};
+
+/// Extrinsic format version supported by the decoder.
+const EXTRINSIC_VERSION: u8 = 4;
+
+/// The signed part of an extrinsic built with `DefaultExtra`.
+#[derive(Clone, Debug, Decode)]
+pub struct SignaturePart {
+    pub address: Address,
+    pub signature: Signature,
+    pub era: Era,
+    pub nonce: Compact<Index>,
+    pub tip: Compact<Balance>,
+}
+
+/// An extrinsic decoded up to the call, the call arguments stay encoded.
+#[derive(Clone, Debug)]
+pub struct DecodedExtrinsic {
+    /// Signature part, `None` for the unsigned extrinsics.
+    pub signature: Option<SignaturePart>,
+    /// SCALE encoded call, starting with the pallet index and call index.
+    pub call: Vec<u8>,
+}
+
+impl DecodedExtrinsic {
+    /// Decodes the opaque extrinsic `xt`.
+    pub fn decode(xt: &OpaqueExtrinsic) -> Result<Self> {
+        // `OpaqueExtrinsic` is encoded as a byte vector.
+        let bytes: Vec<u8> = Decode::decode(&mut xt.encode().as_slice())?;
+        let input = &mut bytes.as_slice();
+
+        let version = u8::decode(input)?;
+        if version & 0b0111_1111 != EXTRINSIC_VERSION {
+            return Err(anyhow!("Unsupported extrinsic version: {}", version));
+        }
+
+        let signature = if version & 0b1000_0000 != 0 {
+            Some(SignaturePart::decode(input)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            signature,
+            call: input.to_vec(),
+        })
+    }
+
+    /// Returns the signer account if the extrinsic is signed by an `AccountId`.
+    pub fn signer(&self) -> Option<&AccountId> {
+        match self.signature {
+            Some(SignaturePart {
+                address: MultiAddress::Id(ref who),
+                ..
+            }) => Some(who),
+            _ => None,
+        }
+    }
+
+    /// Returns the pallet index and call index.
+    pub fn call_index(&self) -> Option<[u8; 2]> {
+        if self.call.len() < 2 {
+            None
+        } else {
+            Some([self.call[0], self.call[1]])
+        }
+    }
+
+    /// Decodes the call arguments as `T` if the call index is `call_index`.
+    pub fn decode_call<T: Decode>(&self, call_index: [u8; 2]) -> Option<T> {
+        if self.call_index() == Some(call_index) {
+            T::decode(&mut &self.call[2..]).ok()
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the events emitted while applying the extrinsic `index`.
+pub fn extrinsic_events(events: &[(Phase, Raw)], index: u32) -> impl Iterator<Item = &Raw> {
+    events.iter().filter_map(move |(phase, raw)| match phase {
+        Phase::ApplyExtrinsic(i) if *i == index => Some(raw),
+        _ => None,
+    })
+}
+
+/// Returns true if the extrinsic `index` has been dispatched successfully.
+pub fn is_extrinsic_success(events: &[(Phase, Raw)], index: u32) -> bool {
+    extrinsic_events(events, index).any(|raw| {
+        matches!(
+            raw,
+            Raw::Event(event) if event.module == "System" && event.variant == "ExtrinsicSuccess"
+        )
+    })
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use codec::{Compact, Decode};
+
+use frame_support::metadata::{DecodeDifferent, RuntimeMetadata, RuntimeMetadataPrefixed};
+use sp_core::H256;
+
+use super::primitives::{AccountId, Address, Balance};
+use crate::token::Token;
+
+/// Name and arguments of a call or an event.
+#[derive(Clone, Debug)]
+pub struct Definition {
+    /// Name of the pallet.
+    pub pallet: String,
+    /// Name of the call or the event.
+    pub name: String,
+    /// Name and type name of each argument, the events only have a type name.
+    pub arguments: Vec<(String, String)>,
+}
+
+/// Names and argument types of the calls and events of the runtime, used to decode
+/// the arguments of any call or event on a best effort basis.
+#[derive(Clone, Debug, Default)]
+pub struct ArgumentRegistry {
+    /// Calls indexed by pallet index and call index.
+    calls: HashMap<[u8; 2], Definition>,
+    /// Events indexed by pallet name and event name.
+    events: HashMap<(String, String), Definition>,
+}
+
+/// A constant of a pallet.
+#[derive(Clone, Debug)]
+pub struct Constant {
+    /// Name of the constant.
+    pub name: String,
+    /// Type name of the constant.
+    pub ty: String,
+    /// SCALE encoded value of the constant.
+    pub value: Vec<u8>,
+    /// Documentation of the constant.
+    pub documentation: Vec<String>,
+}
+
+fn decoded<B, O>(value: &DecodeDifferent<B, O>) -> Result<&O> {
+    match value {
+        DecodeDifferent::Decoded(value) => Ok(value),
+        DecodeDifferent::Encode(_) => Err(anyhow!("Metadata is not decoded")),
+    }
+}
+
+/// Evaluates `$body` with the decoded modules of the runtime metadata `$bytes` bound
+/// to `$modules`, V12 and V13 only differ in the storage layout.
+macro_rules! with_modules {
+    ($bytes:expr, |$modules:ident| $body:expr) => {
+        match RuntimeMetadataPrefixed::decode(&mut $bytes)?.1 {
+            RuntimeMetadata::V12(metadata) => {
+                let $modules = decoded(&metadata.modules)?;
+                $body
+            }
+            RuntimeMetadata::V13(metadata) => {
+                let $modules = decoded(&metadata.modules)?;
+                $body
+            }
+            _ => return Err(anyhow!("Unsupported metadata version")),
+        }
+    };
+}
+
+impl ArgumentRegistry {
+    /// Builds the registry from the SCALE encoded runtime metadata.
+    pub fn from_metadata(mut bytes: &[u8]) -> Result<Self> {
+        let mut registry = Self::default();
+        with_modules!(bytes, |modules| for module in modules {
+            let pallet = decoded(&module.name)?;
+            if let Some(ref calls) = module.calls {
+                for (index, call) in decoded(calls)?.iter().enumerate() {
+                    let arguments = decoded(&call.arguments)?
+                        .iter()
+                        .map(|arg| Ok((decoded(&arg.name)?.clone(), decoded(&arg.ty)?.clone())))
+                        .collect::<Result<_>>()?;
+                    registry.calls.insert(
+                        [module.index, index as u8],
+                        Definition {
+                            pallet: pallet.clone(),
+                            name: decoded(&call.name)?.clone(),
+                            arguments,
+                        },
+                    );
+                }
+            }
+            if let Some(ref events) = module.event {
+                for event in decoded(events)? {
+                    let name = decoded(&event.name)?;
+                    let arguments = decoded(&event.arguments)?
+                        .iter()
+                        .map(|ty| (String::new(), ty.clone()))
+                        .collect();
+                    registry.events.insert(
+                        (pallet.clone(), name.clone()),
+                        Definition {
+                            pallet: pallet.clone(),
+                            name: name.clone(),
+                            arguments,
+                        },
+                    );
+                }
+            }
+        });
+
+        Ok(registry)
+    }
+
+    /// Returns the definition of the encoded `call` and its decoded arguments.
+    pub fn decode_call(&self, token: &Token, call: &[u8]) -> Option<(&Definition, String)> {
+        if call.len() < 2 {
+            return None;
+        }
+        let definition = self.calls.get(&[call[0], call[1]])?;
+        Some((definition, decode_arguments(token, definition, &call[2..])))
+    }
+
+    /// Returns the decoded arguments of the event `name` of `pallet`.
+    pub fn decode_event(&self, token: &Token, pallet: &str, name: &str, data: &[u8]) -> String {
+        match self.events.get(&(pallet.to_string(), name.to_string())) {
+            Some(definition) => decode_arguments(token, definition, data),
+            None => format!("0x{}", hex::encode(data)),
+        }
+    }
+}
+
+/// Returns the constants of `pallet` in the SCALE encoded runtime metadata.
+pub fn pallet_constants(mut bytes: &[u8], pallet: &str) -> Result<Vec<Constant>> {
+    with_modules!(bytes, |modules| {
+        let module = modules
+            .iter()
+            .find(|module| decoded(&module.name).map_or(false, |name| name == pallet))
+            .ok_or_else(|| anyhow!("Pallet {} not found in the metadata", pallet))?;
+        decoded(&module.constants)?
+            .iter()
+            .map(|constant| {
+                Ok(Constant {
+                    name: decoded(&constant.name)?.clone(),
+                    ty: decoded(&constant.ty)?.clone(),
+                    value: decoded(&constant.value)?.clone(),
+                    documentation: decoded(&constant.documentation)?.clone(),
+                })
+            })
+            .collect()
+    })
+}
+
+/// Decodes a value of type `ty`, the value is shown in hex if the type is not supported.
+pub fn decode_value(token: &Token, ty: &str, data: &[u8]) -> String {
+    let mut input = data;
+    match decode_argument(token, ty, &mut input) {
+        Some(value) if input.is_empty() => value,
+        _ => format!("0x{}", hex::encode(data)),
+    }
+}
+
+/// Decodes the arguments as `name: value` pairs, the undecodable remaining bytes are shown in hex.
+fn decode_arguments(token: &Token, definition: &Definition, mut data: &[u8]) -> String {
+    let mut decoded = Vec::new();
+    for (name, ty) in &definition.arguments {
+        let mut input = data;
+        match decode_argument(token, ty, &mut input) {
+            Some(value) if name.is_empty() => decoded.push(value),
+            Some(value) => decoded.push(format!("{}: {}", name, value)),
+            None => break,
+        }
+        data = input;
+    }
+    if !data.is_empty() {
+        decoded.push(format!("0x{}", hex::encode(data)));
+    }
+    decoded.join(", ")
+}
+
+/// Decodes a value of the common types given its type name in the metadata.
+fn decode_argument(token: &Token, ty: &str, input: &mut &[u8]) -> Option<String> {
+    let ty = ty.replace(' ', "");
+    let ty = ty.as_str();
+
+    let is_balance = |ty: &str| ty.ends_with("Balance") || ty.starts_with("BalanceOf");
+    let value = match ty {
+        "bool" => bool::decode(input).ok()?.to_string(),
+        "u8" => u8::decode(input).ok()?.to_string(),
+        "u16" => u16::decode(input).ok()?.to_string(),
+        "u32" | "T::BlockNumber" | "BlockNumber" => u32::decode(input).ok()?.to_string(),
+        "u64" => u64::decode(input).ok()?.to_string(),
+        "u128" => u128::decode(input).ok()?.to_string(),
+        "T::AccountId" | "AccountId" => AccountId::decode(input).ok()?.to_string(),
+        "<T::LookupasStaticLookup>::Source" | "LookupSource" | "Address" => {
+            match Address::decode(input).ok()? {
+                Address::Id(who) => who.to_string(),
+                address => format!("{:?}", address),
+            }
+        }
+        "T::Hash" | "Hash" | "H256" => format!("{:?}", H256::decode(input).ok()?),
+        "Vec<u8>" | "Bytes" => format!("0x{}", hex::encode(Vec::<u8>::decode(input).ok()?)),
+        ty if is_balance(ty) => token.display(Balance::decode(input).ok()?),
+        ty if ty.starts_with("Compact<") && ty.ends_with('>') => {
+            // The compact encoding does not depend on the integer type.
+            let value = Compact::<u128>::decode(input).ok()?.0;
+            if is_balance(&ty["Compact<".len()..ty.len() - 1]) {
+                token.display(value)
+            } else {
+                value.to_string()
+            }
+        }
+        _ => return None,
+    };
+
+    Some(value)
+}
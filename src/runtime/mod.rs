@@ -1,3 +1,5 @@
+pub mod extrinsic;
+pub mod metadata;
 pub mod primitives;
 
 use subxt::{
@@ -10,7 +12,9 @@ use subxt::{
     EventTypeRegistry, PairSigner, Runtime,
 };
 
+use cp_consensus_poa::PoaConfiguration;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use pallet_poa::DepthInfo;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_babe::AuthorityId as BabeId;
 use sp_core::sr25519;
@@ -18,6 +22,11 @@ use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::{generic::Header, impl_opaque_keys, OpaqueExtrinsic};
 
 use self::primitives::*;
+use crate::pallets::{
+    permastore::PermastoreEventTypeRegistry,
+    poa::PoaEventTypeRegistry,
+    utility::{Utility, UtilityEventTypeRegistry},
+};
 
 /// Concrete type definitions for Canyon.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -32,6 +41,11 @@ impl Runtime for CanyonRuntime {
         event_type_registry.with_session();
         // event_type_registry.with_staking();
         event_type_registry.with_sudo();
+        event_type_registry.with_utility();
+        event_type_registry.with_permastore();
+        event_type_registry.with_poa();
+        event_type_registry.register_type_size::<PoaConfiguration>("PoaConfiguration");
+        event_type_registry.register_type_size::<DepthInfo<BlockNumber>>("DepthInfo<BlockNumber>");
         register_default_type_sizes(event_type_registry);
     }
 }
@@ -50,6 +64,8 @@ impl System for CanyonRuntime {
 
 impl Sudo for CanyonRuntime {}
 
+impl Utility for CanyonRuntime {}
+
 impl Balances for CanyonRuntime {
     type Balance = Balance;
 }
@@ -1,7 +1,8 @@
 use sp_core::H256;
 use sp_runtime::{
+    generic,
     traits::{BlakeTwo256, IdentifyAccount, Verify},
-    MultiSignature,
+    MultiSignature, OpaqueExtrinsic,
 };
 
 /// A hash of some data used by the chain.
@@ -35,3 +36,9 @@ pub type Index = u32;
 
 /// Asset ID.
 pub type AssetId = u32;
+
+/// Block header type.
+pub type Header = generic::Header<BlockNumber, Hashing>;
+
+/// Block type, the extrinsics are kept opaque.
+pub type Block = generic::Block<Header, OpaqueExtrinsic>;
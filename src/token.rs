@@ -0,0 +1,272 @@
+use std::{
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::{anyhow, Error, Result};
+use subxt::SystemProperties;
+
+use crate::runtime::primitives::Balance;
+
+/// Whether the balances are parsed and displayed in plancks.
+static RAW: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether the balances are parsed and displayed in plancks instead of tokens.
+pub fn set_raw(raw: bool) {
+    RAW.store(raw, Ordering::Relaxed);
+}
+
+fn is_raw() -> bool {
+    RAW.load(Ordering::Relaxed)
+}
+
+/// Returns the power of ten of the SI prefix `prefix`.
+fn si_exponent(prefix: char) -> Option<i32> {
+    match prefix {
+        'P' => Some(15),
+        'T' => Some(12),
+        'G' => Some(9),
+        'M' => Some(6),
+        'k' => Some(3),
+        'm' => Some(-3),
+        'u' | 'µ' => Some(-6),
+        'n' => Some(-9),
+        'p' => Some(-12),
+        _ => None,
+    }
+}
+
+/// An amount of tokens as typed on the command line, e.g. `1.5`, `1.5 CAN` or `150m`.
+///
+/// The amount is resolved to a balance with the decimals of the connected chain.
+#[derive(Clone, Debug)]
+pub struct Amount {
+    integer: String,
+    fraction: String,
+    unit: String,
+}
+
+impl FromStr for Amount {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let number_len = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '_')
+            .unwrap_or(s.len());
+        let (number, unit) = s.split_at(number_len);
+        let number = number.replace('_', "");
+
+        let (integer, fraction) = match number.split_once('.') {
+            Some((integer, fraction)) => (integer.to_string(), fraction.to_string()),
+            None => (number.clone(), String::new()),
+        };
+        if (integer.is_empty() && fraction.is_empty()) || fraction.contains('.') {
+            return Err(anyhow!("Invalid amount: {}", s));
+        }
+
+        Ok(Self {
+            integer,
+            fraction,
+            unit: unit.trim().into(),
+        })
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.integer)?;
+        if !self.fraction.is_empty() {
+            write!(f, ".{}", self.fraction)?;
+        }
+        if !self.unit.is_empty() {
+            write!(f, " {}", self.unit)?;
+        }
+        Ok(())
+    }
+}
+
+/// Native token of the connected chain.
+#[derive(Clone, Debug)]
+pub struct Token {
+    /// Number of decimals of one token in plancks.
+    pub decimals: u32,
+    /// Symbol of the token.
+    pub symbol: String,
+}
+
+impl Token {
+    pub fn new(properties: &SystemProperties) -> Self {
+        Self {
+            decimals: properties.token_decimals.into(),
+            symbol: properties.token_symbol.clone(),
+        }
+    }
+
+    /// Returns the power of ten of `unit` relative to one token.
+    fn unit_exponent(&self, unit: &str) -> Result<i32> {
+        if unit.is_empty() || unit.eq_ignore_ascii_case(&self.symbol) {
+            return Ok(0);
+        }
+
+        let mut chars = unit.chars();
+        let exponent = chars.next().and_then(si_exponent);
+        let rest = chars.as_str();
+        match exponent {
+            Some(exponent) if rest.is_empty() || rest.eq_ignore_ascii_case(&self.symbol) => {
+                Ok(exponent)
+            }
+            _ => Err(anyhow!(
+                "Unknown unit `{}`, expected {} with an optional SI prefix, e.g. m{}",
+                unit,
+                self.symbol,
+                self.symbol
+            )),
+        }
+    }
+
+    /// Returns the balance in plancks of `amount`.
+    ///
+    /// In raw mode the amount must be an integer number of plancks without unit.
+    pub fn to_balance(&self, amount: &Amount) -> Result<Balance> {
+        if is_raw() {
+            if !amount.fraction.is_empty() || !amount.unit.is_empty() {
+                return Err(anyhow!(
+                    "Raw amounts are integer plancks without unit, got {}",
+                    amount
+                ));
+            }
+            return Ok(amount.integer.parse()?);
+        }
+
+        let scale =
+            self.decimals as i32 + self.unit_exponent(&amount.unit)? - amount.fraction.len() as i32;
+        let mut digits = format!("{}{}", amount.integer, amount.fraction);
+        if scale < 0 {
+            let keep = digits.len().saturating_sub(scale.unsigned_abs() as usize);
+            if digits[keep..].chars().any(|c| c != '0') {
+                return Err(anyhow!("Amount {} is more precise than one planck", amount));
+            }
+            digits.truncate(keep);
+        }
+        let digits = if digits.is_empty() { "0" } else { &digits };
+
+        digits
+            .parse::<Balance>()
+            .ok()
+            .and_then(|balance| {
+                10u128
+                    .checked_pow(scale.max(0) as u32)
+                    .and_then(|multiplier| balance.checked_mul(multiplier))
+            })
+            .ok_or_else(|| anyhow!("Amount {} overflows the balance type", amount))
+    }
+
+    /// Returns `balance` in tokens with the symbol, e.g. `1.5 CAN`, or in plancks in raw mode.
+    pub fn display(&self, balance: Balance) -> String {
+        if is_raw() {
+            return balance.to_string();
+        }
+
+        let unit = 10u128.pow(self.decimals);
+        let integer = balance / unit;
+        let fraction = balance % unit;
+        if fraction == 0 {
+            format!("{} {}", integer, self.symbol)
+        } else {
+            let fraction = format!("{:0width$}", fraction, width = self.decimals as usize);
+            format!(
+                "{}.{} {}",
+                integer,
+                fraction.trim_end_matches('0'),
+                self.symbol
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Mutex, MutexGuard};
+
+    /// Serializes the tests since raw mode is a global setting.
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    fn token(raw: bool) -> (MutexGuard<'static, ()>, Token) {
+        let guard = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        set_raw(raw);
+        let token = Token {
+            decimals: 12,
+            symbol: "CAN".into(),
+        };
+        (guard, token)
+    }
+
+    fn to_balance(token: &Token, amount: &str) -> Result<Balance> {
+        token.to_balance(&amount.parse()?)
+    }
+
+    #[test]
+    fn amounts_are_scaled_with_the_decimals() {
+        let (_guard, token) = token(false);
+        assert_eq!(to_balance(&token, "1").unwrap(), 1_000_000_000_000);
+        assert_eq!(to_balance(&token, "1.5").unwrap(), 1_500_000_000_000);
+        assert_eq!(to_balance(&token, "1.5 CAN").unwrap(), 1_500_000_000_000);
+        assert_eq!(to_balance(&token, "1.5can").unwrap(), 1_500_000_000_000);
+        assert_eq!(to_balance(&token, ".5").unwrap(), 500_000_000_000);
+        assert_eq!(to_balance(&token, "1_000").unwrap(), 1_000_000_000_000_000);
+    }
+
+    #[test]
+    fn amounts_accept_si_prefixes() {
+        let (_guard, token) = token(false);
+        assert_eq!(to_balance(&token, "150m").unwrap(), 150_000_000_000);
+        assert_eq!(to_balance(&token, "150 mCAN").unwrap(), 150_000_000_000);
+        assert_eq!(to_balance(&token, "2k").unwrap(), 2_000_000_000_000_000);
+        assert_eq!(to_balance(&token, "1p").unwrap(), 1);
+        assert!(to_balance(&token, "1 DOT").is_err());
+    }
+
+    #[test]
+    fn amounts_more_precise_than_one_planck_are_rejected() {
+        let (_guard, token) = token(false);
+        assert!(to_balance(&token, "0.0000000000001").is_err());
+        assert!(to_balance(&token, "1.5p").is_err());
+        assert_eq!(to_balance(&token, "0.0000000000010").unwrap(), 1);
+    }
+
+    #[test]
+    fn amounts_overflowing_the_balance_are_rejected() {
+        let (_guard, token) = token(false);
+        assert!(to_balance(&token, "1000000000000000000000000000000").is_err());
+        assert!(to_balance(&token, "1000000000000000000P").is_err());
+    }
+
+    #[test]
+    fn invalid_amounts_are_rejected() {
+        assert!(".".parse::<Amount>().is_err());
+        assert!("1.2.3".parse::<Amount>().is_err());
+        assert!("CAN".parse::<Amount>().is_err());
+    }
+
+    #[test]
+    fn raw_amounts_are_integer_plancks() {
+        let (_guard, token) = token(true);
+        assert_eq!(to_balance(&token, "15").unwrap(), 15);
+        assert!(to_balance(&token, "1.5").is_err());
+        assert!(to_balance(&token, "15 CAN").is_err());
+        assert!(to_balance(&token, "15m").is_err());
+        assert_eq!(token.display(15), "15");
+    }
+
+    #[test]
+    fn balances_are_displayed_in_tokens() {
+        let (_guard, token) = token(false);
+        assert_eq!(token.display(1_500_000_000_000), "1.5 CAN");
+        assert_eq!(token.display(2_000_000_000_000), "2 CAN");
+        assert_eq!(token.display(1), "0.000000000001 CAN");
+    }
+}
@@ -1,12 +1,23 @@
-use std::{fs::File, io::Read, path::Path};
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::Path,
+    time::Duration,
+};
 
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use codec::Encode;
+use structopt::clap::arg_enum;
 
-use sp_core::crypto::{Pair, Public, Ss58Codec};
+use sp_core::{
+    crypto::{Pair, Public, Ss58Codec},
+    hashing::twox_128,
+};
 use sp_keyring::AccountKeyring;
-use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT, IdentifyAccount, Verify};
 
-use crate::runtime::primitives::{AccountId, Signature};
+use crate::runtime::primitives::{AccountId, Hash, Signature};
 
 /// Returns all bytes of `code_path`.
 pub fn read_code<P: AsRef<Path>>(code_path: P) -> Result<Vec<u8>> {
@@ -33,6 +44,186 @@ pub fn parse_account(address: &str) -> Result<AccountId> {
     }
 }
 
+/// Returns the merkle root of `data` split in chunks of `chunk_size` bytes.
+pub fn compute_chunk_root(data: &[u8], chunk_size: u32) -> Hash {
+    let chunks = data
+        .chunks(chunk_size as usize)
+        .map(|c| BlakeTwo256::hash(c).encode())
+        .collect();
+    BlakeTwo256::ordered_trie_root(chunks)
+}
+
+/// Returns the non-empty lines of `path`, lines starting with `#` are ignored.
+pub fn read_lines<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(Into::into)
+        .collect())
+}
+
+/// Writes `data` to a temporary file first so that an interruption never leaves a partial file.
+pub fn write_atomically(path: &Path, data: &[u8]) -> Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, data)?;
+    fs::rename(tmp_path, path)?;
+    Ok(())
+}
+
+/// Parses a 32 bytes hash from a hex string, the `0x` prefix is optional.
+pub fn parse_hash(hash: &str) -> Result<Hash> {
+    let stripped = hash.strip_prefix("0x").unwrap_or(hash);
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(stripped, &mut bytes as &mut [u8])
+        .map_err(|err| anyhow!("Failed to parse hash {}: {:?}", hash, err))?;
+    Ok(bytes.into())
+}
+
+/// Asks the user to confirm `prompt` on the terminal, returns true if confirmed.
+pub fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Returns the storage prefix of `storage_prefix` in pallet `pallet_prefix`.
+pub fn final_storage_prefix(pallet_prefix: &str, storage_prefix: &str) -> Vec<u8> {
+    let mut final_prefix = twox_128(pallet_prefix.as_bytes()).to_vec();
+    final_prefix.extend_from_slice(&twox_128(storage_prefix.as_bytes()));
+    final_prefix
+}
+
+arg_enum! {
+    /// Output format of the tabular reports.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum OutputFormat {
+        Table,
+        Csv,
+        Json,
+    }
+}
+
+impl OutputFormat {
+    /// Prints `rows` under `header` in this format.
+    pub fn print(&self, header: &[&str], rows: &[Vec<String>]) {
+        match self {
+            Self::Table => print_table(header, rows),
+            Self::Csv => print_csv(header, rows),
+            Self::Json => print_json(header, rows),
+        }
+    }
+}
+
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths = header.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.len());
+        }
+    }
+
+    let format_row = |fields: Vec<&str>| {
+        fields
+            .iter()
+            .zip(&widths)
+            .map(|(field, width)| format!("{:<width$}", field, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(header.to_vec()));
+    for row in rows {
+        println!("{}", format_row(row.iter().map(String::as_str).collect()));
+    }
+}
+
+fn print_csv(header: &[&str], rows: &[Vec<String>]) {
+    println!("{}", csv_record(header));
+    for row in rows {
+        println!("{}", csv_record(row));
+    }
+}
+
+fn print_json(header: &[&str], rows: &[Vec<String>]) {
+    let records = rows
+        .iter()
+        .map(|row| {
+            header
+                .iter()
+                .zip(row)
+                .map(|(key, value)| (key.to_string(), value.clone().into()))
+                .collect::<serde_json::Map<_, _>>()
+        })
+        .collect::<Vec<_>>();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&records).expect("string map is serializable; qed")
+    );
+}
+
+/// Returns a CSV record of `fields`, quoting the fields when necessary.
+pub fn csv_record<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains(&[',', '"', '\n', '\r'][..]) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Initial delay before resubscribing.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maximum delay before resubscribing.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A subscription recreated by [`subscribe_with_backoff`] whenever it fails.
+#[async_trait(?Send)]
+pub trait Resubscribe {
+    /// Subscribes until the subscription fails or ends.
+    ///
+    /// `connected` is set once the subscription has been established.
+    async fn run_subscription(&mut self, url: &str, connected: &mut bool) -> Result<()>;
+}
+
+/// Runs the subscription of `subscriber` forever, reconnecting with an exponential backoff
+/// if the connection is lost, the backoff starts over once a subscription is established.
+pub async fn subscribe_with_backoff<S: Resubscribe>(
+    url: &str,
+    what: &str,
+    subscriber: &mut S,
+) -> Result<()> {
+    let mut backoff = MIN_BACKOFF;
+
+    loop {
+        let mut connected = false;
+        if let Err(err) = subscriber.run_subscription(url, &mut connected).await {
+            if connected {
+                backoff = MIN_BACKOFF;
+            }
+            eprintln!(
+                "Watching {} interrupted: {:?}, reconnecting in {}s",
+                what,
+                err,
+                backoff.as_secs()
+            );
+            async_std::task::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
+
 type AccountPublic = <Signature as Verify>::Signer;
 
 /// Helper function to generate a crypto pair from seed